@@ -1,9 +1,12 @@
 use std::{error, f64, marker, path};
 #[cfg(any(feature = "svg", feature = "png"))]
-use std::{fs, io};
+use std::{collections::BTreeMap, fs, io};
 #[cfg(feature = "svg")]
 use std::env;
 
+// re-export cairo, so the context type used by `CairoCanvas::from_context` can be named
+pub use cairo;
+
 /// Converts a Cairo error to a draw error.
 fn convert_err<E: error::Error + marker::Sync + marker::Send + 'static>(
     e: E,
@@ -11,7 +14,19 @@ fn convert_err<E: error::Error + marker::Sync + marker::Send + 'static>(
     draw::DrawError::BackendError(e.into())
 }
 
+/// Creates a draw error for a Cairo surface that is not of the expected type.
+#[cfg(any(feature = "svg", feature = "png"))]
+fn surface_err(expected: &str) -> draw::DrawError {
+    draw::DrawError::BackendError(anyhow::anyhow!(
+        "Cairo context target is not an {} surface", expected
+    ))
+}
+
 /// The Cairo backend for `plt`.
+///
+/// Cairo's SVG surface has no option to emit `<text>` elements, so text in SVG output is
+/// written as glyph outlines. Labels are not selectable or editable as text, but the
+/// output does not depend on fonts installed where it is viewed.
 #[derive(Debug)]
 pub struct CairoCanvas {
     size: draw::Size,
@@ -84,7 +99,7 @@ impl draw::Canvas for CairoCanvas {
             desc.face_color.a,
         );
 
-        context.paint().unwrap();
+        context.paint().map_err(convert_err)?;
 
         Ok(Self {
             size: desc.size,
@@ -184,7 +199,7 @@ impl draw::Canvas for CairoCanvas {
         );
         self.context.set_line_width(desc.line_width as f64);
 
-        self.context.set_dash(desc.dashes, 0.0);
+        self.context.set_dash(desc.dashes, desc.dash_offset);
 
         let offset = if desc.line_width % 2 == 0 { 0.0 } else { 0.5 };
 
@@ -216,7 +231,7 @@ impl draw::Canvas for CairoCanvas {
         self.context.set_line_width(desc.line_width as f64);
         self.context.set_line_join(cairo::LineJoin::Round);
 
-        self.context.set_dash(desc.dashes, 0.0);
+        self.context.set_dash(desc.dashes, desc.dash_offset);
 
         let offset = if desc.line_width % 2 == 0 { 0.0 } else { 0.5 };
 
@@ -292,6 +307,19 @@ impl draw::Canvas for CairoCanvas {
         let extents = self.context.text_extents(&desc.text).map_err(convert_err)?;
 
         let position = align_text(position, desc.rotation, extents, desc.alignment);
+        // middle alignments center on digits rather than the text's own ink, keeping a shared baseline
+        let position = if let draw::Alignment::MiddleLeft | draw::Alignment::MiddleRight = desc.alignment {
+            let digit = self.context.text_extents("0").map_err(convert_err)?;
+            let offset = (extents.y_bearing() + extents.height() / 2.0)
+                - (digit.y_bearing() + digit.height() / 2.0);
+
+            CairoPoint {
+                x: position.x - offset * desc.rotation.sin(),
+                y: position.y + offset * desc.rotation.cos(),
+            }
+        } else {
+            position
+        };
         self.context.move_to(position.x, position.y);
 
         self.context.save().map_err(convert_err)?;
@@ -346,61 +374,16 @@ impl draw::Canvas for CairoCanvas {
                 match desc.format {
                     #[cfg(feature = "png")]
                     draw::FileFormat::Png => {
-                        // temporarily remove surface from context
-                        let mut surface = cairo::ImageSurface::try_from(
-                            self.context.target()
-                        )
-                        .unwrap();
-                        let blank_surface = cairo::ImageSurface::create(
-                            cairo::Format::ARgb32,
-                            0,
-                            0,
-                        )
-                        .map_err(convert_err)?;
-                        self.context = cairo::Context::new(&blank_surface).map_err(convert_err)?;
-
+                        // open file before touching the surface, so a bad path leaves the canvas intact
                         let file = fs::File::create(desc.filename)?;
-                        let w = &mut io::BufWriter::new(file);
-
-                        // configure encoder
-                        let mut encoder = png::Encoder::new(
-                            w,
-                            self.size.width,
-                            self.size.height,
-                        );
-                        encoder.set_color(png::ColorType::Rgba);
-                        encoder.set_depth(png::BitDepth::Eight);
-                        let mut writer = encoder.write_header().map_err(convert_err)?;
-
-                        // extract buffer from cairo
-                        let buffer_raw = surface.data().map_err(convert_err)?;
-                        // fix color byte ordering
-                        let buffer = buffer_raw.chunks(4)
-                            .flat_map(|rgba| [rgba[2], rgba[1], rgba[0], rgba[3]])
-                            .collect::<Vec<_>>();
-
-                        // set dpi
-                        let ppu = (desc.dpi as f64 * (1000.0 / 25.4)) as u32;
-                        let xppu = ppu.to_be_bytes();
-                        let yppu = ppu.to_be_bytes();
-                        let unit = png::Unit::Meter;
-                        writer.write_chunk(
-                            png::chunk::pHYs,
-                            &[
-                                xppu[0], xppu[1], xppu[2], xppu[3],
-                                yppu[0], yppu[1], yppu[2], yppu[3],
-                                unit as u8,
-                            ],
-                        )
-                        .map_err(convert_err)?;
-
-                        writer.write_image_data(&buffer[..]).map_err(convert_err)?;
-
-                        drop(buffer_raw);
-                        drop(buffer);
-
-                        // return surface to self
-                        self.context = cairo::Context::new(&surface).map_err(convert_err)?;
+
+                        self.write_png(
+                            io::BufWriter::new(file),
+                            desc.dpi,
+                            &desc.metadata,
+                            desc.compression,
+                            desc.srgb,
+                        )?;
                     },
                     #[cfg(not(feature = "png"))]
                     draw::FileFormat::Png => {
@@ -420,20 +403,9 @@ impl draw::Canvas for CairoCanvas {
                 #[cfg(feature = "svg")]
                 match desc.format {
                     draw::FileFormat::Svg => {
-                        // finish writing file
-                        let old_surface = cairo::SvgSurface::try_from(
-                            self.context.target()
-                        )
-                        .unwrap();
-                        old_surface.finish();
-
-                        if let Some(temp_file) = &self.temp_file {
-                            // copy temp file to new specified location
-                            fs::copy(temp_file, desc.filename.as_ref())?;
-
-                            // remove temp file
-                            fs::remove_file(temp_file)?;
-                        }
+                        let file = fs::File::create(desc.filename)?;
+
+                        self.write_svg(io::BufWriter::new(file), &desc.metadata)?;
                     },
                     file_format => {
                         return Err(draw::DrawError::UnsupportedFileFormat(
@@ -457,11 +429,123 @@ impl draw::Canvas for CairoCanvas {
         #[allow(unreachable_code)]
         Ok(())
     }
+    fn clear(&mut self, color: draw::Color) -> Result<(), draw::DrawError> {
+        self.context.save().map_err(convert_err)?;
+
+        // replace existing pixels, including their transparency
+        self.context.reset_clip();
+        self.context.set_operator(cairo::Operator::Source);
+        self.context.set_source_rgba(color.r, color.g, color.b, color.a);
+        self.context.paint().map_err(convert_err)?;
+
+        self.context.restore().map_err(convert_err)?;
+
+        Ok(())
+    }
+    fn set_antialias(&mut self, antialias: bool) -> Result<(), draw::DrawError> {
+        self.context.set_antialias(if antialias {
+            cairo::Antialias::Default
+        } else {
+            cairo::Antialias::None
+        });
+
+        Ok(())
+    }
+    #[cfg_attr(not(any(feature = "png", feature = "svg")), allow(unused_variables))]
+    fn write_image<W: std::io::Write>(
+        &mut self,
+        writer: W,
+        desc: draw::WriteImageDescriptor,
+    ) -> Result<(), draw::DrawError> {
+        match self.image_format {
+            draw::ImageFormat::Bitmap => {
+                match desc.format {
+                    #[cfg(feature = "png")]
+                    draw::FileFormat::Png => {
+                        self.write_png(writer, desc.dpi, &desc.metadata, desc.compression, desc.srgb)
+                    },
+                    file_format => {
+                        Err(draw::DrawError::UnsupportedFileFormat(format!(
+                            "{:?} is not supported by the Cairo backend for bitmap images",
+                            file_format,
+                        )))
+                    },
+                }
+            },
+            draw::ImageFormat::Svg => {
+                match desc.format {
+                    #[cfg(feature = "svg")]
+                    draw::FileFormat::Svg => self.write_svg(writer, &desc.metadata),
+                    file_format => {
+                        Err(draw::DrawError::UnsupportedFileFormat(
+                            format!("{:?} is not supported for svg images", file_format)
+                        ))
+                    },
+                }
+            },
+            image_format => {
+                Err(draw::DrawError::UnsupportedImageFormat(
+                    format!("{:?} is not supported by the Cairo backend", image_format)
+                ))
+            }
+        }
+    }
     fn size(&self) -> Result<draw::Size, draw::DrawError> {
         Ok(self.size)
     }
 }
 impl CairoCanvas {
+    /// Encodes the image surface as a PNG into a writer.
+    #[cfg(feature = "png")]
+    fn write_png<W: io::Write>(
+        &mut self,
+        w: W,
+        dpi: u16,
+        metadata: &BTreeMap<String, String>,
+        compression: Option<u8>,
+        srgb: bool,
+    ) -> Result<(), draw::DrawError> {
+        // temporarily remove surface from context
+        let mut surface = cairo::ImageSurface::try_from(self.context.target())
+            .map_err(|_| surface_err("image"))?;
+        let blank_surface = cairo::ImageSurface::create(cairo::Format::ARgb32, 0, 0)
+            .map_err(convert_err)?;
+        self.context = cairo::Context::new(&blank_surface).map_err(convert_err)?;
+
+        let result = encode_png(&mut surface, w, self.size, dpi, metadata, compression, srgb);
+
+        // return surface to self, even if encoding failed
+        self.context = cairo::Context::new(&surface).map_err(convert_err)?;
+
+        result
+    }
+    /// Finishes the SVG surface and writes the document into a writer.
+    #[cfg(feature = "svg")]
+    fn write_svg<W: io::Write>(
+        &mut self,
+        mut w: W,
+        metadata: &BTreeMap<String, String>,
+    ) -> Result<(), draw::DrawError> {
+        // finish writing temp file
+        let surface = cairo::SvgSurface::try_from(self.context.target())
+            .map_err(|_| surface_err("svg"))?;
+        surface.finish();
+
+        if let Some(temp_file) = &self.temp_file {
+            let svg = fs::read_to_string(temp_file)?;
+            if metadata.is_empty() {
+                w.write_all(svg.as_bytes())?;
+            } else {
+                w.write_all(svg_with_metadata(&svg, metadata).as_bytes())?;
+            }
+            w.flush()?;
+
+            // remove temp file
+            fs::remove_file(temp_file)?;
+        }
+
+        Ok(())
+    }
     fn reset_clip(&mut self) {
         self.context.reset_clip();
     }
@@ -498,6 +582,98 @@ impl CairoPoint {
     }
 }
 
+#[cfg(feature = "png")]
+fn encode_png<W: io::Write>(
+    surface: &mut cairo::ImageSurface,
+    w: W,
+    size: draw::Size,
+    dpi: u16,
+    metadata: &BTreeMap<String, String>,
+    compression: Option<u8>,
+    srgb: bool,
+) -> Result<(), draw::DrawError> {
+    // configure encoder
+    let mut encoder = png::Encoder::new(w, size.width, size.height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    // png only offers coarse compression presets
+    if let Some(level) = compression {
+        encoder.set_compression(match level {
+            0..=3 => png::Compression::Fast,
+            4..=6 => png::Compression::Default,
+            _ => png::Compression::Best,
+        });
+    }
+    for (key, value) in metadata {
+        // tEXt chunks only hold Latin-1 text, anything else needs a UTF-8 iTXt chunk
+        if value.chars().all(|c| c <= '\u{ff}') {
+            encoder.add_text_chunk(key.clone(), value.clone()).map_err(convert_err)?;
+        } else {
+            encoder.add_itxt_chunk(key.clone(), value.clone()).map_err(convert_err)?;
+        }
+    }
+    let mut writer = encoder.write_header().map_err(convert_err)?;
+
+    // extract buffer from cairo
+    let buffer_raw = surface.data().map_err(convert_err)?;
+    // fix color byte ordering
+    let buffer = buffer_raw.chunks(4)
+        .flat_map(|rgba| [rgba[2], rgba[1], rgba[0], rgba[3]])
+        .collect::<Vec<_>>();
+
+    // set dpi
+    let ppu = (dpi as f64 * (1000.0 / 25.4)) as u32;
+    let xppu = ppu.to_be_bytes();
+    let yppu = ppu.to_be_bytes();
+    let unit = png::Unit::Meter;
+    writer.write_chunk(
+        png::chunk::pHYs,
+        &[
+            xppu[0], xppu[1], xppu[2], xppu[3],
+            yppu[0], yppu[1], yppu[2], yppu[3],
+            unit as u8,
+        ],
+    )
+    .map_err(convert_err)?;
+
+    // cairo draws in sRGB, tag it along with the matching gamma for older decoders
+    if srgb {
+        let intent = 0; // perceptual
+        writer.write_chunk(png::chunk::sRGB, &[intent]).map_err(convert_err)?;
+        writer.write_chunk(png::chunk::gAMA, &45455u32.to_be_bytes()).map_err(convert_err)?;
+    }
+
+    writer.write_image_data(&buffer[..]).map_err(convert_err)?;
+
+    Ok(())
+}
+
+/// Inserts a `<title>` and `<metadata>` element at the start of an SVG document.
+#[cfg(feature = "svg")]
+fn svg_with_metadata(svg: &str, metadata: &BTreeMap<String, String>) -> String {
+    // insert after the opening svg tag
+    let insert_at = match svg.find("<svg").and_then(|start| svg[start..].find('>').map(|end| start + end + 1)) {
+        Some(index) => index,
+        None => return svg.to_owned(),
+    };
+
+    let mut elements = String::new();
+    if let Some(title) = metadata.get("Title") {
+        elements += &format!("\n<title>{}</title>", draw::xml_escape(title));
+    }
+    elements += "\n<metadata>";
+    for (key, value) in metadata {
+        elements += &format!(
+            "\n<entry key=\"{}\">{}</entry>",
+            draw::xml_escape(key),
+            draw::xml_escape(value),
+        );
+    }
+    elements += "\n</metadata>";
+
+    format!("{}{}{}", &svg[..insert_at], elements, &svg[insert_at..])
+}
+
 fn font_to_cairo(name: draw::FontName) -> String {
     match name {
         draw::FontName::FreeSans => "freesans".to_owned(),
@@ -528,20 +704,14 @@ fn align_text(
     alignment: draw::Alignment,
 ) -> CairoPoint {
     let (x, y) = match alignment {
-        draw::Alignment::Center => (
-            position.x - (extents.x_bearing() + extents.width() / 2.0)*rotation.cos()
-                + (extents.y_bearing() + extents.height() / 2.0)*rotation.sin(),
-            position.y - (extents.y_bearing() + extents.height() / 2.0)*rotation.cos()
-                - (extents.x_bearing() + extents.width() / 2.0)*rotation.sin(),
-        ),
-        draw::Alignment::Right => (
+        draw::Alignment::Right | draw::Alignment::MiddleRight => (
             position.x - extents.x_bearing()*rotation.cos()
                 - extents.width()*rotation.cos().clamp(0.0, 1.0)
                 + extents.y_bearing()*rotation.sin().clamp(0.0, 1.0),
             position.y - (extents.y_bearing() + (extents.height() / 2.0))*rotation.cos()
                 - (extents.x_bearing() + extents.width() / 2.0)*rotation.sin(),
         ),
-        draw::Alignment::Left => (
+        draw::Alignment::Left | draw::Alignment::MiddleLeft => (
             position.x - extents.x_bearing()*rotation.cos()
                 - extents.width()*rotation.cos().clamp(-1.0, 0.0)
                 + extents.y_bearing()*rotation.sin()
@@ -605,7 +775,146 @@ fn align_text(
                 + extents.x_bearing()*rotation.sin()
                 - extents.width()*rotation.sin().clamp(0.0, 1.0),
         ),
+        // centered, also for any alignment added to plt-draw later
+        _ => (
+            position.x - (extents.x_bearing() + extents.width() / 2.0)*rotation.cos()
+                + (extents.y_bearing() + extents.height() / 2.0)*rotation.sin(),
+            position.y - (extents.y_bearing() + extents.height() / 2.0)*rotation.cos()
+                - (extents.x_bearing() + extents.width() / 2.0)*rotation.sin(),
+        ),
     };
 
     CairoPoint { x, y }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use draw::Canvas;
+
+    const SIZE: draw::Size = draw::Size { width: 100, height: 100 };
+
+    /// Draws onto a white bitmap canvas, returning the color of each pixel, by rows from the top.
+    fn render<F: FnOnce(&mut CairoCanvas)>(draw_fn: F) -> Vec<Vec<[u8; 3]>> {
+        let mut surface = cairo::ImageSurface::create(
+            cairo::Format::ARgb32,
+            SIZE.width as i32,
+            SIZE.height as i32,
+        )
+        .unwrap();
+        {
+            let context = cairo::Context::new(&surface).unwrap();
+            context.set_source_rgba(1.0, 1.0, 1.0, 1.0);
+            context.paint().unwrap();
+
+            let mut canvas = CairoCanvas::from_context(&context, SIZE, draw::ImageFormat::Bitmap);
+            draw_fn(&mut canvas);
+        }
+        surface.flush();
+
+        let stride = surface.stride() as usize;
+        let data = surface.data().unwrap();
+        (0..SIZE.height as usize)
+            .map(|row| (0..SIZE.width as usize)
+                .map(|col| {
+                    // cairo stores native endian ARGB words
+                    let offset = row * stride + col * 4;
+                    let argb = u32::from_ne_bytes(data[offset..offset + 4].try_into().unwrap());
+                    [(argb >> 16) as u8, (argb >> 8) as u8, argb as u8]
+                })
+                .collect())
+            .collect()
+    }
+
+    /// A square region filled with a color.
+    fn square(xmin: f64, ymin: f64, l: f64, fill_color: draw::Color) -> draw::FillDescriptor {
+        draw::FillDescriptor {
+            points: vec![
+                draw::Point { x: xmin, y: ymin },
+                draw::Point { x: xmin, y: ymin + l },
+                draw::Point { x: xmin + l, y: ymin + l },
+                draw::Point { x: xmin + l, y: ymin },
+            ],
+            fill_color,
+            clip_area: None,
+        }
+    }
+
+    #[test]
+    fn overlapping_translucent_fills_blend() {
+        let red = draw::Color { a: 0.5, ..draw::Color::RED };
+        let blue = draw::Color { a: 0.5, ..draw::Color::BLUE };
+
+        let pixels = render(|canvas| {
+            canvas.fill_region(square(10.0, 10.0, 50.0, red)).unwrap();
+            canvas.fill_region(square(40.0, 40.0, 50.0, blue)).unwrap();
+        });
+
+        // overlap of both squares, rows counted from the top
+        let [r, g, b] = pixels[50][50];
+        assert!(r > 100 && b > 100 && g < 100, "overlap is not purple: {:?}", [r, g, b]);
+        // each square alone is only tinted by its own fill
+        let [r, g, b] = pixels[75][20];
+        assert!(r > 200 && g < 150 && b < 150, "red square is {:?}", [r, g, b]);
+        let [r, g, b] = pixels[20][80];
+        assert!(b > 200 && r < 150 && g < 150, "blue square is {:?}", [r, g, b]);
+    }
+
+    #[test]
+    fn thick_zig_zag_has_no_spike() {
+        let pixels = render(|canvas| {
+            // a sharp peak at y = 80, 20 rows from the top, where a miter join would spike
+            canvas.draw_curve(draw::CurveDescriptor {
+                points: vec![
+                    draw::Point { x: 40.0, y: 20.0 },
+                    draw::Point { x: 50.0, y: 80.0 },
+                    draw::Point { x: 60.0, y: 20.0 },
+                ],
+                line_width: 6,
+                line_color: draw::Color::BLACK,
+                dashes: &[],
+                dash_offset: 0.0,
+                clip_area: None,
+            })
+            .unwrap();
+        });
+
+        let is_dark = |[r, g, b]: [u8; 3]| r < 128 && g < 128 && b < 128;
+        // the join reaches about half the line width above the peak, and no further
+        assert!(pixels[18].iter().any(|&pixel| is_dark(pixel)));
+        for (row, colors) in pixels[..16].iter().enumerate() {
+            assert!(!colors.iter().any(|&pixel| is_dark(pixel)), "line spikes up to row {}", row);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "png")]
+    fn png_metadata_outside_latin1_is_written_as_itxt() {
+        let size = draw::Size { width: 2, height: 2 };
+        let mut surface = cairo::ImageSurface::create(
+            cairo::Format::ARgb32,
+            size.width as i32,
+            size.height as i32,
+        )
+        .unwrap();
+        let metadata = BTreeMap::from([
+            ("Author".to_owned(), "Zoë".to_owned()),
+            ("Title".to_owned(), "Σ of ∞ series".to_owned()),
+        ]);
+
+        let mut buffer = vec![];
+        encode_png(&mut surface, &mut buffer, size, 100, &metadata, None, false).unwrap();
+
+        let reader = png::Decoder::new(buffer.as_slice()).read_info().unwrap();
+        let info = reader.info();
+        let text = info.uncompressed_latin1_text.iter()
+            .map(|chunk| (chunk.keyword.as_str(), chunk.text.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(text, [("Author", "Zoë")]);
+        let utf8_text = info.utf8_text.iter()
+            .map(|chunk| (chunk.keyword.as_str(), chunk.get_text().unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(utf8_text, [("Title", "Σ of ∞ series".to_owned())]);
+    }
+}
+
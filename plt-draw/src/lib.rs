@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::{io, path};
 
 /// The error type for this library.
@@ -51,13 +52,13 @@ pub struct Area {
     pub ymax: u32,
 }
 impl Area {
-    /// Get the width of the area.
+    /// Get the width of the area, or zero if the bounds are reversed.
     pub fn xsize(&self) -> u32 {
-        self.xmax - self.xmin
+        self.xmax.saturating_sub(self.xmin)
     }
-    /// Get the height of the area.
+    /// Get the height of the area, or zero if the bounds are reversed.
     pub fn ysize(&self) -> u32 {
-        self.ymax - self.ymin
+        self.ymax.saturating_sub(self.ymin)
     }
     /// Convert a fractional point, to a dot (pixel) point.
     pub fn fractional_to_point(&self, frac: Point) -> Point {
@@ -175,6 +176,7 @@ impl Default for FontWeight {
 }
 
 /// How something should be aligned.
+#[non_exhaustive]
 #[derive(Copy, Clone, Debug)]
 pub enum Alignment {
     /// Aligned to the center.
@@ -195,6 +197,12 @@ pub enum Alignment {
     BottomLeft,
     /// Aligned to the bottom right corner.
     BottomRight,
+    /// Aligned to the left side, centered vertically on the height of digits above the baseline.
+    /// Unlike [`Alignment::Left`], text lines up along its baseline regardless of descenders.
+    MiddleLeft,
+    /// Aligned to the right side, centered vertically on the height of digits above the baseline.
+    /// Unlike [`Alignment::Right`], text lines up along its baseline regardless of descenders.
+    MiddleRight,
 }
 
 /// A graphics image file format.
@@ -279,6 +287,8 @@ pub struct LineDescriptor<'a> {
     pub line_color: Color,
     /// How the line will be dashed.
     pub dashes: &'a [f64],
+    /// How far into the dash pattern the line starts.
+    pub dash_offset: f64,
     /// Optionally clip drawing to some area.
     pub clip_area: Option<Area>,
 }
@@ -292,6 +302,7 @@ impl Default for LineDescriptor<'_> {
             line_width: 2,
             line_color: Color::BLACK,
             dashes: &[],
+            dash_offset: 0.0,
             clip_area: None,
         }
     }
@@ -307,6 +318,8 @@ pub struct CurveDescriptor<'a> {
     pub line_color: Color,
     /// How the line will be dashed.
     pub dashes: &'a [f64],
+    /// How far into the dash pattern the line starts.
+    pub dash_offset: f64,
     /// Optionally clip drawing to some area.
     pub clip_area: Option<Area>,
 }
@@ -317,6 +330,7 @@ impl Default for CurveDescriptor<'_> {
             line_width: 2,
             line_color: Color::BLACK,
             dashes: &[],
+            dash_offset: 0.0,
             clip_area: None,
         }
     }
@@ -332,13 +346,22 @@ pub struct TextDescriptor {
     pub position: Point,
     /// The color of the text.
     pub color: Color,
-    /// How the text should be rotated.
+    /// How the text should be rotated, in radians.
+    /// See [`Self::with_rotation_degrees`] to set it in degrees.
     pub rotation: f64,
     /// What side of the text to align to the position.
     pub alignment: Alignment,
     /// Optionally clip drawing to some area.
     pub clip_area: Option<Area>,
 }
+impl TextDescriptor {
+    /// Sets how the text should be rotated, in degrees.
+    pub fn with_rotation_degrees(mut self, degrees: f64) -> Self {
+        self.rotation = degrees.to_radians();
+
+        self
+    }
+}
 impl Default for TextDescriptor {
     fn default() -> Self {
         Self {
@@ -365,6 +388,8 @@ pub struct FillDescriptor {
 }
 
 /// Describes how to save the image to a file.
+/// Created with [`SaveFileDescriptor::new`], so options can be added without breaking backends.
+#[non_exhaustive]
 #[derive(Clone, Debug)]
 pub struct SaveFileDescriptor<P: AsRef<path::Path>> {
     /// The name of the output file.
@@ -373,6 +398,56 @@ pub struct SaveFileDescriptor<P: AsRef<path::Path>> {
     pub format: FileFormat,
     /// The dots (pixels) per inch.
     pub dpi: u16,
+    /// Key-value metadata to embed in the file, such as `Title` or `Author`.
+    pub metadata: BTreeMap<String, String>,
+    /// Compression level for bitmap files, from 0 (fastest) to 9 (smallest).
+    /// Uses the backend default if [`None`].
+    pub compression: Option<u8>,
+    /// Whether to tag bitmap files as sRGB, so viewers and printers agree on their colors.
+    pub srgb: bool,
+}
+impl<P: AsRef<path::Path>> SaveFileDescriptor<P> {
+    /// Describes saving to a file at 100 DPI, with no metadata, default compression and no sRGB tag.
+    pub fn new(filename: P, format: FileFormat) -> Self {
+        Self {
+            filename,
+            format,
+            dpi: 100,
+            metadata: BTreeMap::new(),
+            compression: None,
+            srgb: false,
+        }
+    }
+}
+
+/// Describes how to write the image to a writer.
+/// Created with [`WriteImageDescriptor::new`], so options can be added without breaking backends.
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub struct WriteImageDescriptor {
+    /// The image format to write.
+    pub format: FileFormat,
+    /// The dots (pixels) per inch.
+    pub dpi: u16,
+    /// Key-value metadata to embed in the image, such as `Title` or `Author`.
+    pub metadata: BTreeMap<String, String>,
+    /// Compression level for bitmap images, from 0 (fastest) to 9 (smallest).
+    /// Uses the backend default if [`None`].
+    pub compression: Option<u8>,
+    /// Whether to tag bitmap images as sRGB, so viewers and printers agree on their colors.
+    pub srgb: bool,
+}
+impl WriteImageDescriptor {
+    /// Describes writing an image at 100 DPI, with no metadata, default compression and no sRGB tag.
+    pub fn new(format: FileFormat) -> Self {
+        Self {
+            format,
+            dpi: 100,
+            metadata: BTreeMap::new(),
+            compression: None,
+            srgb: false,
+        }
+    }
 }
 
 /// Represents a structure used for drawing.
@@ -396,6 +471,48 @@ pub trait Canvas {
         &mut self,
         desc: SaveFileDescriptor<P>,
     ) -> Result<(), DrawError>;
+    /// Write the image to a writer, such as a buffer or network stream.
+    /// Returns an error by default, for backends that can only save files.
+    fn write_image<W: io::Write>(
+        &mut self,
+        _writer: W,
+        desc: WriteImageDescriptor,
+    ) -> Result<(), DrawError> {
+        Err(DrawError::UnsupportedFileFormat(format!(
+            "writing {:?} images to a writer is not supported by this backend",
+            desc.format,
+        )))
+    }
+    /// Replaces the whole image with a color, for reusing the canvas between frames.
+    /// By default, fills the canvas area, which cannot make existing content transparent.
+    fn clear(&mut self, color: Color) -> Result<(), DrawError> {
+        let size = self.size()?;
+        let (width, height) = (size.width as f64, size.height as f64);
+
+        self.fill_region(FillDescriptor {
+            points: vec![
+                Point { x: 0.0, y: 0.0 },
+                Point { x: width, y: 0.0 },
+                Point { x: width, y: height },
+                Point { x: 0.0, y: height },
+            ],
+            fill_color: color,
+            clip_area: None,
+        })
+    }
+    /// Sets whether following drawing is antialiased.
+    /// Ignored by default, for backends without control over antialiasing.
+    fn set_antialias(&mut self, _antialias: bool) -> Result<(), DrawError> {
+        Ok(())
+    }
     /// Get canvas size.
     fn size(&self) -> Result<Size, DrawError>;
 }
+
+/// Escapes text for use in XML or HTML content and attributes, such as metadata written into images.
+pub fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -1,12 +1,15 @@
 use crate::backend;
 use crate::layout::{FractionalArea, Layout};
+use crate::overlay::Overlay;
 use crate::subplot::{
-    AxisType, Grid, Line, LineStyle, MarkerStyle, PlotType, Subplot, TickDirection, TickLabels, TickSpacing,
+    check_limits, check_major_tick_marks, nice_limits, nice_ticks, AxisType, Grid, GridLayer, Limits,
+    Line, LineStyle, MarkerStyle, PlotType, Subplot, SubplotFormat, TickDirection, TickLabels,
+    TickSpacing,
 };
-use crate::{Color, FileFormat, PltError};
+use crate::{Color, FileFormat, FontName, PltError, Size};
 
-use std::collections::HashMap;
-use std::{f64, iter, marker, ops, path};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::{f64, io, iter, marker, ops, path};
 
 /// Represents a whole figure, containing subplots, which can be drawn as an image.
 ///
@@ -19,28 +22,44 @@ pub struct Figure<'a, B: backend::Canvas = backend::CairoCanvas> {
     size: draw::Size,
     scaling: f32,
     dpi: u16,
+    scale_factor: f32,
     face_color: Color,
+    subplot_size: Option<FigSize>,
+    metadata: BTreeMap<String, String>,
+    png_compression: Option<u8>,
+    png_srgb: bool,
+    subplot_format: Option<SubplotFormat>,
+    max_points_per_series: Option<usize>,
+    reserve_title_space: bool,
     phantom: marker::PhantomData<B>,
 }
 #[cfg(not(feature = "cairo"))]
 pub struct Figure<'a, B: backend::Canvas> {
     subplots: Vec<Subplot<'a>>,
-    subplot_areas: Vec<draw::Area>,
+    subplot_areas: Vec<FractionalArea>,
     size: draw::Size,
     scaling: f32,
     dpi: u16,
+    scale_factor: f32,
     face_color: Color,
+    subplot_size: Option<FigSize>,
+    metadata: BTreeMap<String, String>,
+    png_compression: Option<u8>,
+    png_srgb: bool,
+    subplot_format: Option<SubplotFormat>,
+    max_points_per_series: Option<usize>,
+    reserve_title_space: bool,
     phantom: marker::PhantomData<B>,
 }
 impl<'a, B: backend::Canvas> Figure<'a, B> {
     /// The main constructor.
     pub fn new(format: &FigureFormat) -> Self {
-        // scaling factor for different DPIs
-        let scaling = format.dpi as f32 / FigureFormat::default().dpi as f32;
+        // scaling factor for different DPIs and output scales
+        let scaling = format.dpi as f32 / FigureFormat::default().dpi as f32 * format.scale_factor;
 
         // size of figure in pixels
-        let width = (format.size.width * format.dpi as f32).floor() as u32;
-        let height = (format.size.height * format.dpi as f32).floor() as u32;
+        let width = (format.size.width * format.dpi as f32 * format.scale_factor).floor() as u32;
+        let height = (format.size.height * format.dpi as f32 * format.scale_factor).floor() as u32;
 
         Self {
             subplots: Vec::new(),
@@ -48,7 +67,15 @@ impl<'a, B: backend::Canvas> Figure<'a, B> {
             size: draw::Size { width, height },
             scaling,
             dpi: format.dpi,
+            scale_factor: format.scale_factor,
             face_color: format.face_color,
+            subplot_size: format.subplot_size,
+            metadata: format.metadata.clone(),
+            png_compression: format.png_compression,
+            png_srgb: format.png_srgb,
+            subplot_format: None,
+            max_points_per_series: format.max_points_per_series,
+            reserve_title_space: format.reserve_title_space,
             phantom: marker::PhantomData,
         }
     }
@@ -63,25 +90,63 @@ impl<'a, B: backend::Canvas> Figure<'a, B> {
             return Err(PltError::InvalidSubplotArea(*area));
         }
 
+        // only fill in subplots that were not given their own format
+        if let Some(format) = &self.subplot_format {
+            for subplot in subplots.iter_mut().filter(|subplot| !subplot.format_set) {
+                subplot.format = format.clone();
+            }
+        }
+
         self.subplots.append(&mut subplots);
         self.subplot_areas.append(&mut frac_areas);
 
+        if let Some(subplot_size) = self.subplot_size {
+            self.fit_to_subplots(subplot_size);
+        }
+
+        Ok(())
+    }
+
+    /// Sets a format for subplots added after this call that were not given a format of their own.
+    pub fn set_subplot_defaults(&mut self, format: SubplotFormat) -> Result<(), PltError> {
+        format.validate()?;
+        self.subplot_format = Some(format);
+
         Ok(())
     }
 
     /// Draw figure to provided backend.
     pub fn draw_to_backend(&mut self, backend: &mut B) -> Result<(), PltError> {
-        let old_size = self.size;
-        self.size = backend.size()?;
+        // paint background over any previous frame, matching a new canvas
+        backend.clear(self.face_color)?;
 
-        for (subplot, subplot_area) in iter::zip(&self.subplots, &self.subplot_areas) {
-            let subplot_area = subplot_area.to_area(self.size);
-            draw_subplot(backend, subplot, &subplot_area, self.scaling)?;
-        }
+        let size = backend.size()?;
+        let area = draw::Area { xmin: 0, xmax: size.width, ymin: 0, ymax: size.height };
+        self.draw_subplots(backend, area)
+    }
 
-        self.size = old_size;
+    /// Draw figure into an area of the provided backend, leaving the rest of the canvas as it is.
+    /// Useful for placing a figure among other drawing, or tiling several figures on one canvas.
+    pub fn draw_to_backend_area(
+        &mut self,
+        backend: &mut B,
+        area: draw::Area,
+    ) -> Result<(), PltError> {
+        let (xmin, xmax) = (area.xmin as f64, area.xmax as f64);
+        let (ymin, ymax) = (area.ymin as f64, area.ymax as f64);
+
+        backend.fill_region(draw::FillDescriptor {
+            points: vec![
+                draw::Point { x: xmin, y: ymin },
+                draw::Point { x: xmax, y: ymin },
+                draw::Point { x: xmax, y: ymax },
+                draw::Point { x: xmin, y: ymax },
+            ],
+            fill_color: self.face_color,
+            clip_area: None,
+        })?;
 
-        Ok(())
+        self.draw_subplots(backend, area)
     }
 
     /// Draw figure to a file.
@@ -90,6 +155,77 @@ impl<'a, B: backend::Canvas> Figure<'a, B> {
         format: FileFormat,
         filename: P,
     ) -> Result<(), PltError> {
+        if let FileFormat::Png = format {
+            check_png_metadata(&self.metadata)?;
+        }
+        let mut canvas = self.draw_canvas(format)?;
+
+        // save to file
+        let mut desc = draw::SaveFileDescriptor::new(filename.as_ref(), format);
+        desc.dpi = self.dpi;
+        desc.metadata = self.metadata.clone();
+        desc.compression = self.png_compression;
+        desc.srgb = self.png_srgb;
+        canvas.save_file(desc)?;
+
+        Ok(())
+    }
+
+    /// Draw figure to a writer, such as a buffer or network stream.
+    pub fn write_to<W: io::Write>(&self, writer: W, format: FileFormat) -> Result<(), PltError> {
+        if let FileFormat::Png = format {
+            check_png_metadata(&self.metadata)?;
+        }
+        let mut canvas = self.draw_canvas(format)?;
+
+        let mut desc = draw::WriteImageDescriptor::new(format);
+        desc.dpi = self.dpi;
+        desc.metadata = self.metadata.clone();
+        desc.compression = self.png_compression;
+        desc.srgb = self.png_srgb;
+        canvas.write_image(writer, desc)?;
+
+        Ok(())
+    }
+
+    /// Draw figure to a standalone HTML file, with the figure embedded as an inline SVG image.
+    /// The page title is taken from the `Title` metadata, if set.
+    pub fn save_html<P: AsRef<path::Path>>(&self, filename: P) -> Result<(), PltError> {
+        let mut svg = Vec::new();
+        self.write_to(&mut svg, FileFormat::Svg)?;
+        let svg = String::from_utf8_lossy(&svg);
+
+        // the XML declaration is not allowed inside an HTML document
+        let svg = match svg.trim_start().strip_prefix("<?xml") {
+            Some(rest) => rest.split_once("?>").map_or(rest, |(_, svg)| svg),
+            None => &svg,
+        };
+        let title = self.metadata.get("Title").map_or("Figure", |title| title.as_str());
+
+        let html = format!(
+            "<!DOCTYPE html>\n\
+            <html>\n\
+            <head>\n\
+            <meta charset=\"utf-8\">\n\
+            <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+            <title>{}</title>\n\
+            <style>svg {{ max-width: 100%; height: auto; }}</style>\n\
+            </head>\n\
+            <body>\n\
+            {}\n\
+            </body>\n\
+            </html>\n",
+            draw::xml_escape(title),
+            svg.trim(),
+        );
+
+        std::fs::write(filename, html).map_err(draw::DrawError::from)?;
+
+        Ok(())
+    }
+
+    /// Creates a canvas suitable for a file format and draws the figure to it.
+    fn draw_canvas(&self, format: FileFormat) -> Result<B, PltError> {
         // create canvas to draw to
         let image_format = match format {
             FileFormat::Png => draw::ImageFormat::Bitmap,
@@ -102,19 +238,63 @@ impl<'a, B: backend::Canvas> Figure<'a, B> {
             image_format,
         })?;
 
+        let area = draw::Area { xmin: 0, xmax: self.size.width, ymin: 0, ymax: self.size.height };
+        self.draw_subplots(&mut canvas, area)?;
+
+        Ok(canvas)
+    }
+
+    /// Draws each subplot in its part of the provided area of a canvas.
+    fn draw_subplots(&self, canvas: &mut B, area: draw::Area) -> Result<(), PltError> {
+        let size = draw::Size { width: area.xsize(), height: area.ysize() };
         for (subplot, subplot_area) in iter::zip(&self.subplots, &self.subplot_areas) {
-            let subplot_area = subplot_area.to_area(self.size);
-            draw_subplot(&mut canvas, subplot, &subplot_area, self.scaling)?;
+            // offset from the area origin
+            let subplot_area = subplot_area.to_area(size);
+            let subplot_area = draw::Area {
+                xmin: subplot_area.xmin + area.xmin,
+                xmax: subplot_area.xmax + area.xmin,
+                ymin: subplot_area.ymin + area.ymin,
+                ymax: subplot_area.ymax + area.ymin,
+            };
+            draw_subplot(
+                canvas,
+                subplot,
+                &subplot_area,
+                self.scaling,
+                self.max_points_per_series,
+                self.reserve_title_space,
+            )?;
         }
 
-        // save to file
-        canvas.save_file(draw::SaveFileDescriptor {
-            filename: filename.as_ref(),
-            format,
-            dpi: self.dpi,
+        Ok(())
+    }
+
+    /// Returns the size in pixels that text will take up when drawn in this figure.
+    /// The font size is scaled by the figure DPI, as with [`SubplotFormat::font_size`].
+    pub fn measure_text(
+        &self,
+        text: &str,
+        font_name: &FontName,
+        font_size: f32,
+    ) -> Result<Size, PltError> {
+        // a small canvas is enough for measuring
+        let mut canvas = B::new(draw::CanvasDescriptor {
+            size: draw::Size { width: 1, height: 1 },
+            face_color: self.face_color,
+            image_format: draw::ImageFormat::Bitmap,
         })?;
 
-        Ok(())
+        let size = canvas.text_size(draw::TextDescriptor {
+            text: text.to_owned(),
+            font: draw::Font {
+                name: font_name.clone(),
+                size: font_size * self.scaling,
+                ..Default::default()
+            },
+            ..Default::default()
+        })?;
+
+        Ok(size)
     }
 
     /// Get reference to held subplots.
@@ -128,18 +308,77 @@ impl<'a, B: backend::Canvas> Figure<'a, B> {
 
     /// Change size of figure.
     pub fn set_size(&mut self, size: FigSize) {
-        let width = (size.width * self.dpi as f32).floor() as u32;
-        let height = (size.height * self.dpi as f32).floor() as u32;
+        let width = (size.width * self.dpi as f32 * self.scale_factor).floor() as u32;
+        let height = (size.height * self.dpi as f32 * self.scale_factor).floor() as u32;
 
         self.size = draw::Size { width, height };
     }
 
+    /// Resizes the figure so each subplot area is at least the provided size.
+    fn fit_to_subplots(&mut self, subplot_size: FigSize) {
+        let (width, height) = self.subplot_areas.iter()
+            .fold((0.0f32, 0.0f32), |(width, height), area| (
+                f32::max(width, subplot_size.width / (area.xmax - area.xmin) as f32),
+                f32::max(height, subplot_size.height / (area.ymax - area.ymin) as f32),
+            ));
+
+        if width > 0.0 && height > 0.0 {
+            self.set_size(FigSize { width, height });
+        }
+    }
+
     /// Removes all subplots from figure.
     pub fn clear(&mut self) {
         self.subplots.clear();
         self.subplot_areas.clear();
     }
 }
+#[cfg(feature = "cairo")]
+impl<'a> Figure<'a, backend::CairoCanvas> {
+    /// Draws the figure onto an existing Cairo context, such as one shared with other drawing.
+    /// The figure fills `area`, given in canvas coordinates as for [`Self::draw_to_backend_area`],
+    /// where y increases upwards from the bottom of the context's clip extents,
+    /// which is the bottom of its surface unless a clip is set.
+    pub fn draw_to_cairo(
+        &self,
+        context: &draw_cairo::cairo::Context,
+        area: draw::Area,
+    ) -> Result<(), PltError> {
+        let cairo_err = |e: draw_cairo::cairo::Error| draw::DrawError::BackendError(e.into());
+
+        // cairo's y increases downwards, so find the top of the area from the bottom of the context
+        let (_, _, _, bottom) = context.clip_extents().map_err(cairo_err)?;
+
+        // draw relative to the area, leaving the context as it was
+        context.save().map_err(cairo_err)?;
+        context.translate(area.xmin as f64, bottom - area.ymax as f64);
+
+        let size = draw::Size { width: area.xsize(), height: area.ysize() };
+        let mut canvas = backend::CairoCanvas::from_context(context, size, draw::ImageFormat::Bitmap);
+
+        // paint the face color over the area only, unlike clearing the whole surface
+        let (width, height) = (size.width as f64, size.height as f64);
+        let result = backend::Canvas::fill_region(&mut canvas, draw::FillDescriptor {
+            points: vec![
+                draw::Point { x: 0.0, y: 0.0 },
+                draw::Point { x: width, y: 0.0 },
+                draw::Point { x: width, y: height },
+                draw::Point { x: 0.0, y: height },
+            ],
+            fill_color: self.face_color,
+            clip_area: None,
+        })
+        .map_err(PltError::from)
+        .and_then(|_| self.draw_subplots(
+            &mut canvas,
+            draw::Area { xmin: 0, xmax: size.width, ymin: 0, ymax: size.height },
+        ));
+
+        context.restore().map_err(cairo_err)?;
+
+        result
+    }
+}
 impl<'a, B: backend::Canvas> Default for Figure<'a, B> {
     fn default() -> Self {
         Self::new(&FigureFormat::default())
@@ -153,15 +392,66 @@ pub struct FigureFormat {
     pub size: FigSize,
     /// The dots (pixels) per inch of the figure.
     pub dpi: u16,
+    /// Multiplies the number of pixels drawn, keeping the size and DPI stored in saved files.
+    /// For example, `2.0` renders crisply on high density displays. Should be positive.
+    pub scale_factor: f32,
     /// The background color of the figure.
     pub face_color: Color,
+    /// If set, the figure is resized when a layout is set so each subplot has this size, in inches.
+    /// Overrides `size`.
+    pub subplot_size: Option<FigSize>,
+    /// Key-value metadata embedded in saved files, such as `Title` or `Author`.
+    /// For PNG files, keys must be 1 to 79 printable Latin-1 characters.
+    pub metadata: BTreeMap<String, String>,
+    /// PNG compression level, from 0 (fastest) to 9 (smallest). Uses the encoder default if [`None`].
+    pub png_compression: Option<u8>,
+    /// Whether to tag PNG files as sRGB, so viewers and print workflows agree on their colors.
+    pub png_srgb: bool,
+    /// If set, series with more points are thinned to about this many points when drawn.
+    /// Step plots are never thinned.
+    pub max_points_per_series: Option<usize>,
+    /// If true, space for a title is kept above every subplot, even those without one.
+    /// Keeps plot areas aligned across a grid where only some subplots have titles.
+    pub reserve_title_space: bool,
+}
+impl FigureFormat {
+    /// A figure fitting a single journal column, 3.5 inches wide and 2.625 inches tall.
+    pub fn single_column() -> Self {
+        Self {
+            size: FigSize { width: 3.5, height: 2.625 },
+            ..Default::default()
+        }
+    }
+
+    /// A figure spanning two journal columns, 7.0 inches wide and 4.5 inches tall.
+    pub fn double_column() -> Self {
+        Self {
+            size: FigSize { width: 7.0, height: 4.5 },
+            ..Default::default()
+        }
+    }
+
+    /// A square figure, 5.0 inches on each side.
+    pub fn square() -> Self {
+        Self {
+            size: FigSize { width: 5.0, height: 5.0 },
+            ..Default::default()
+        }
+    }
 }
 impl Default for FigureFormat {
     fn default() -> Self {
         Self {
             size: FigSize { width: 6.75, height: 5.00 },
             dpi: 100,
+            scale_factor: 1.0,
             face_color: Color::WHITE,
+            subplot_size: None,
+            metadata: BTreeMap::new(),
+            png_compression: None,
+            png_srgb: false,
+            max_points_per_series: None,
+            reserve_title_space: false,
         }
     }
 }
@@ -172,6 +462,21 @@ pub struct FigSize {
     pub width: f32,
     pub height: f32,
 }
+impl FigSize {
+    /// Creates a size from a width and height in centimeters.
+    pub fn from_cm(width: f32, height: f32) -> Self {
+        Self { width: width / 2.54, height: height / 2.54 }
+    }
+
+    /// Creates a size from a width and height in pixels at the provided DPI.
+    pub fn from_pixels(width: u32, height: u32, dpi: u16) -> Self {
+        // add half a pixel so rounding down to whole pixels gives back the requested size
+        Self {
+            width: (width as f32 + 0.5) / dpi as f32,
+            height: (height as f32 + 0.5) / dpi as f32,
+        }
+    }
+}
 
 // private
 
@@ -204,6 +509,39 @@ struct AxisFinalized {
     pub minor_grid: bool,
     pub limits: (f64, f64),
     pub visible: bool,
+    pub line_color: Color,
+}
+
+/// A small deterministic random number generator.
+struct SplitMix64(u64);
+impl SplitMix64 {
+    /// Returns a random value in the range [0, 1).
+    fn next_f64(&mut self) -> f64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^= z >> 31;
+
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Checks that metadata keys are valid PNG text keywords, which the encoder would otherwise reject.
+fn check_png_metadata(metadata: &BTreeMap<String, String>) -> Result<(), PltError> {
+    for key in metadata.keys() {
+        if key.is_empty() || key.chars().count() > 79 {
+            return Err(PltError::InvalidData(format!(
+                "metadata key `{}` should be 1 to 79 characters long to be saved in a PNG file", key
+            )));
+        } else if !key.chars().all(|c| matches!(c, ' '..='~' | '\u{a1}'..='\u{ff}')) {
+            return Err(PltError::InvalidData(format!(
+                "metadata key `{}` should only have printable Latin-1 characters to be saved in a PNG file", key
+            )));
+        }
+    }
+
+    Ok(())
 }
 
 fn sigdigit(num: f64) -> i32 {
@@ -229,6 +567,18 @@ fn sigdigit(num: f64) -> i32 {
     }
 }
 
+/// Rounds limits outward to a number of significant figures of their extent.
+pub(crate) fn round_limits((min, max): (f64, f64), sig_figs: u8) -> (f64, f64) {
+    if max <= min {
+        return (min, max);
+    }
+
+    let place = sig_figs as i32 - 1 - sigdigit(max - min);
+    let scale = f64::powi(10.0, place);
+
+    ((min * scale).floor() / scale, (max * scale).ceil() / scale)
+}
+
 fn decimals(mut num: f64, ndigits: u8) -> Vec<u8> {
     let mut decimals = Vec::with_capacity(ndigits as usize);
     for _ in 0..ndigits {
@@ -361,6 +711,41 @@ fn tick_modifiers(ticks: &[f64]) -> Result<(f64, i32, usize), PltError> {
     Ok((offset, multiplier, precision))
 }
 
+/// Modifiers for minor tick labels, sharing the offset and multiplier of the major tick labels.
+/// Minor ticks fall between major ticks, so they may need more decimal places.
+fn minor_tick_modifiers(
+    minor_ticks: &[f64],
+    major_modifiers: (f64, i32, usize),
+) -> Result<(f64, i32, usize), PltError> {
+    // make sure there are no NaNs
+    if minor_ticks.iter().any(|&tick| tick.is_nan()) {
+        return Err(PltError::BadTickPlacement("tick is NaN".to_owned()));
+    }
+
+    let (offset, multiplier, major_precision) = major_modifiers;
+
+    // subdividing major ticks needs at most a couple more decimal places
+    let max_precision = major_precision + 2;
+    let precision = minor_ticks.iter()
+        .map(|&tick| {
+            let tick = round_to(tick - offset, 4 - multiplier);
+            let shifted = if multiplier != 0 {
+                (tick * f64::powi(10.0, 3 - multiplier)).round() * f64::powi(10.0, -3)
+            } else {
+                tick
+            };
+
+            decimals(round_to(shifted, max_precision as i32), max_precision as u8)
+                .iter()
+                .rposition(|&digit| digit != 0)
+                .map(|prec| prec + 1)
+                .unwrap_or(0)
+        })
+        .fold(major_precision, usize::max);
+
+    Ok((offset, multiplier, precision))
+}
+
 fn ticks_to_labels(ticks: &[f64], modifiers: (f64, i32, usize)) -> Result<Vec<String>, PltError> {
     // make sure there are no NaNs
     if ticks.iter().any(|&tick| tick.is_nan()) {
@@ -374,10 +759,8 @@ fn ticks_to_labels(ticks: &[f64], modifiers: (f64, i32, usize)) -> Result<Vec<St
 
     let (offset, multiplier, precision) = modifiers;
 
-    // sort ticks
+    // labels stay in the same order as their ticks
     let mut ticks = ticks.to_vec();
-    ticks.sort_by(|a, b| a.partial_cmp(b).unwrap());
-
     for tick in ticks.iter_mut() {
         *tick = round_to(*tick - offset, 4 - multiplier);
     }
@@ -401,12 +784,199 @@ fn ticks_to_labels(ticks: &[f64], modifiers: (f64, i32, usize)) -> Result<Vec<St
     Ok(labels)
 }
 
+/// Draws lines between consecutive points as separate segments,
+/// with the width and color of each segment given by its index.
+/// Segments without a width are left out.
+/// Dashes continue along the whole path, as if it were one curve.
+fn draw_segments<B: backend::Canvas>(
+    canvas: &mut B,
+    points: &[draw::Point],
+    width: impl Fn(usize) -> Option<u32>,
+    color: impl Fn(usize) -> Color,
+    dashes: &[f64],
+    dash_offset: f64,
+    clip_area: draw::Area,
+) -> Result<(), PltError> {
+    let mut dash_offset = dash_offset;
+    for (n, segment) in points.windows(2).enumerate() {
+        if let Some(line_width) = width(n) {
+            canvas.draw_line(draw::LineDescriptor {
+                line: draw::Line { p1: segment[0], p2: segment[1] },
+                line_width,
+                line_color: color(n),
+                dashes,
+                dash_offset,
+                clip_area: Some(clip_area),
+            })?;
+        }
+
+        dash_offset += f64::hypot(segment[1].x - segment[0].x, segment[1].y - segment[0].y);
+    }
+
+    Ok(())
+}
+
+/// Splits the region between two curves into polygons to fill.
+/// Curves sharing x-values are split where they cross, so no polygon crosses itself.
+/// Other curves are filled as one polygon, following the first curve and back along the second.
+fn fill_polygons(curve1: &[(f64, f64)], curve2: &[(f64, f64)]) -> Vec<Vec<(f64, f64)>> {
+    let shared_x = curve1.len() == curve2.len()
+        && iter::zip(curve1, curve2).all(|(p1, p2)| p1.0 == p2.0);
+    if !shared_x {
+        return vec![curve1.iter().chain(curve2.iter().rev()).copied().collect()];
+    }
+
+    let mut polygons = vec![];
+    let (mut run1, mut run2) = (vec![], vec![]);
+    for n in 0..curve1.len() {
+        if n > 0 {
+            let d0 = curve1[n - 1].1 - curve2[n - 1].1;
+            let d1 = curve1[n].1 - curve2[n].1;
+
+            // close the polygon where the curves cross, and start the next one there
+            if d0 * d1 < 0.0 {
+                let t = d0 / (d0 - d1);
+                let crossing = (
+                    curve1[n - 1].0 + t * (curve1[n].0 - curve1[n - 1].0),
+                    curve1[n - 1].1 + t * (curve1[n].1 - curve1[n - 1].1),
+                );
+
+                run1.push(crossing);
+                polygons.push(run1.drain(..).chain(run2.drain(..).rev()).collect());
+                run1.push(crossing);
+            }
+        }
+
+        run1.push(curve1[n]);
+        run2.push(curve2[n]);
+    }
+    polygons.push(run1.into_iter().chain(run2.into_iter().rev()).collect());
+
+    polygons
+}
+
+/// The position of the `n`th of `count` items, from 0 for the first to 1 for the last.
+fn index_fraction(n: usize, count: usize) -> f64 {
+    if count > 1 {
+        n as f64 / (count - 1) as f64
+    } else {
+        0.0
+    }
+}
+
+/// Draws grid lines for each axis across the plot area.
+fn draw_grid<B: backend::Canvas>(
+    canvas: &mut B,
+    finalized_axes: &BTreeMap<AxisType, AxisFinalized>,
+    plot_area: &draw::Area,
+    line_width: u32,
+    grid_color: Color,
+) -> Result<(), PltError> {
+    for (placement, axis) in finalized_axes.iter() {
+        // draw ticks
+        for (ticks, grid) in [
+            (&axis.major_tick_locs, &axis.major_grid),
+            (&axis.minor_tick_locs, &axis.minor_grid),
+        ] {
+            // convert tick numbers to pixel locations
+            let tick_locs = ticks.iter()
+                // convert to fraction
+                .map(|tick| (tick - axis.limits.0) / (axis.limits.1 - axis.limits.0))
+                // convert to pixel
+                .map(|frac| plot_area.fractional_to_point(draw::Point { x: frac, y: frac }))
+                .collect::<Vec<_>>();
+
+            // draw grid lines
+            if *grid {
+                for loc in tick_locs.iter() {
+                    let line = match placement {
+                        AxisType::Y | AxisType::SecondaryY => draw::Line {
+                            p1: draw::Point {
+                                x: plot_area.xmin as f64,
+                                y: loc.y.round(),
+                            },
+                            p2: draw::Point {
+                                x: plot_area.xmax as f64,
+                                y: loc.y.round(),
+                            },
+                        },
+                        AxisType::X | AxisType::SecondaryX => draw::Line {
+                            p1: draw::Point {
+                                x: loc.x.round(),
+                                y: plot_area.ymin as f64,
+                            },
+                            p2: draw::Point {
+                                x: loc.x.round(),
+                                y: plot_area.ymax as f64,
+                            },
+                        },
+                    };
+                    canvas.draw_line(draw::LineDescriptor {
+                        line,
+                        line_color: grid_color,
+                        line_width,
+                        ..Default::default()
+                    })?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Draws a triangle at the edge of the plot area, pointing towards data beyond the limits.
+fn draw_clip_indicator<B: backend::Canvas>(
+    canvas: &mut B,
+    plot_area: &draw::Area,
+    frac: draw::Point,
+    tip: draw::Point,
+    size: f64,
+    color: Color,
+) -> Result<(), PltError> {
+    // direction the indicator points
+    let (dx, dy) = if frac.y > 1.0 {
+        (0.0, 1.0)
+    } else if frac.y < 0.0 {
+        (0.0, -1.0)
+    } else if frac.x > 1.0 {
+        (1.0, 0.0)
+    } else {
+        (-1.0, 0.0)
+    };
+
+    // base of the triangle is inside the plot area
+    let base = draw::Point { x: tip.x - dx * 1.5 * size, y: tip.y - dy * 1.5 * size };
+
+    canvas.fill_region(draw::FillDescriptor {
+        points: vec![
+            tip,
+            draw::Point { x: base.x + dy * size, y: base.y + dx * size },
+            draw::Point { x: base.x - dy * size, y: base.y - dx * size },
+        ],
+        fill_color: color,
+        clip_area: Some(*plot_area),
+    })?;
+
+    Ok(())
+}
+
 fn draw_subplot<B: backend::Canvas>(
     canvas: &mut B,
     subplot: &Subplot,
     subplot_area: &draw::Area,
     scaling: f32,
+    max_points: Option<usize>,
+    reserve_title_space: bool,
 ) -> Result<(), PltError> {
+    // very small figures can round a subplot down to nothing
+    if subplot_area.xmax <= subplot_area.xmin || subplot_area.ymax <= subplot_area.ymin {
+        return Err(PltError::SubplotTooSmall {
+            width: subplot_area.xmax.saturating_sub(subplot_area.xmin),
+            height: subplot_area.ymax.saturating_sub(subplot_area.ymin),
+        });
+    }
+
     // set formatting parameters
 
     // line formatting
@@ -423,42 +993,28 @@ fn draw_subplot<B: backend::Canvas>(
     // colors
     let default_marker_color = subplot.format.default_marker_color;
     let default_fill_color = subplot.format.default_fill_color;
+    let band_alpha = subplot.format.band_alpha;
+
+    // tick formatting, returning inner and outer major, then inner and outer minor tick lengths
+    // an axis tick length overrides the format, with minor ticks half as long
+    let tick_lengths = |placement: AxisType| {
+        let axis_tick_length = subplot.axis(placement).tick_length;
+        let major = axis_tick_length.unwrap_or(subplot.format.tick_length) * scaling.round() as u32;
+        let minor = match (axis_tick_length, subplot.format.override_minor_tick_length) {
+            (None, Some(length)) => length * scaling.round() as u32,
+            _ => major / 2,
+        };
 
-    // major tick formatting
-    let inner_major_tick_length = match subplot.format.tick_direction {
-        TickDirection::Inner | TickDirection::Both => {
-            subplot.format.tick_length * scaling.round() as u32
-        },
-        _ => 0,
-    };
-    let outer_major_tick_length = match subplot.format.tick_direction {
-        TickDirection::Outer | TickDirection::Both => {
-            subplot.format.tick_length * scaling.round() as u32
-        },
-        _ => 0,
-    };
-    // minor tick formatting
-    let inner_minor_tick_length = match subplot.format.tick_direction {
-        TickDirection::Inner | TickDirection::Both => {
-            if let Some(length) = subplot.format.override_minor_tick_length {
-                length * scaling.round() as u32
-            } else {
-                subplot.format.tick_length * scaling.round() as u32 / 2
-            }
-        },
-        _ => 0,
-    };
-    let outer_minor_tick_length = match subplot.format.tick_direction {
-        TickDirection::Outer | TickDirection::Both => {
-            if let Some(length) = subplot.format.override_minor_tick_length {
-                length * scaling.round() as u32
-            } else {
-                subplot.format.tick_length * scaling.round() as u32 / 2
-            }
-        },
-        _ => 0,
+        match subplot.format.tick_direction {
+            TickDirection::Inner => (major, 0, minor, 0),
+            TickDirection::Outer => (0, major, 0, minor),
+            TickDirection::Both => (major, major, minor, minor),
+        }
     };
 
+    // minimum pixel distance between automatic major ticks
+    let min_tick_spacing = subplot.format.min_tick_spacing * scaling.round() as u32;
+
     // layout depends on the font size
     let letter_size = canvas.text_size(draw::TextDescriptor {
         text: format!("{}", 0),
@@ -476,6 +1032,9 @@ fn draw_subplot<B: backend::Canvas>(
 
     // the pixel buffer sizes for fitting text on the figure sides
     let buffer_offset = ((letter_size.height as f64) * 0.6) as u32;
+    // the gap between ticks and their labels
+    let tick_label_pad = subplot.format.tick_label_pad
+        .map_or(buffer_offset, |pad| pad * scaling.round() as u32);
     let mut subplot_buffer = HashMap::from([
         (AxisType::Y, 0),
         (AxisType::SecondaryY, 0),
@@ -508,65 +1067,76 @@ fn draw_subplot<B: backend::Canvas>(
     ]);
 
     // get ticks and tick labels
-    let mut finalized_axes = HashMap::<AxisType, AxisFinalized>::new();
+    // ordered so axes are always drawn in the same order
+    let mut finalized_axes = BTreeMap::<AxisType, AxisFinalized>::new();
     for placement in AxisType::iter() {
-        let axis = match placement {
-            AxisType::Y => &subplot.yaxis,
-            AxisType::X => &subplot.xaxis,
-            AxisType::SecondaryY => &subplot.secondary_yaxis,
-            AxisType::SecondaryX => &subplot.secondary_xaxis,
-        };
+        let axis = subplot.axis(placement);
+        let (_, outer_major_tick_length, _, outer_minor_tick_length) = tick_lengths(placement);
 
         // get span and limits for each axis, if None, use values from opposite side
-        let (span, limits) = if let (Some(span), Some(limits)) = (axis.span, axis.limits) {
-            (span, limits)
+        let (span, limits) = subplot.span_and_limits(placement);
+        let is_nice = matches!(axis.limit_policy, Limits::AutoNice);
+
+        // reversed limits would otherwise silently draw mirrored data
+        check_limits(placement, limits)?;
+        check_major_tick_marks(placement, &axis.major_tick_marks)?;
+
+        let is_primary = subplot.axis_in_use(placement);
+
+        // most automatic major ticks keeping the minimum spacing
+        // measured over the subplot area, as the plot area depends on the ticks
+        // nice limits are covered by ticks from end to end
+        let axis_length = match placement {
+            AxisType::X | AxisType::SecondaryX => subplot_area.xsize(),
+            AxisType::Y | AxisType::SecondaryY => subplot_area.ysize(),
+        } as f64 * if is_nice { 1.0 } else { (span.1 - span.0) / (limits.1 - limits.0) };
+        let max_auto_ticks = if min_tick_spacing > 0 {
+            u16::max(((axis_length / min_tick_spacing as f64).floor() as u16).saturating_add(1), 2)
         } else {
-            // use opposite side, if it has a value, otherwise default to (-1.0, 1.0)
-            let opposite_axis = match placement {
-                AxisType::X => {
-                    &subplot.secondary_xaxis
-                },
-                AxisType::SecondaryX => {
-                    &subplot.xaxis
-                },
-                AxisType::Y => {
-                    &subplot.secondary_yaxis
-                },
-                AxisType::SecondaryY => {
-                    &subplot.yaxis
-                },
-            };
+            u16::MAX
+        };
 
-            if let (Some(span), Some(limits)) = (opposite_axis.span, opposite_axis.limits) {
-                (span, limits)
-            } else {
-                ((-1.0, 1.0), (-1.0, 1.0))
-            }
+        let nticks = match &axis.major_tick_marks {
+            TickSpacing::Count(n) => *n,
+            TickSpacing::On => u16::min(5, max_auto_ticks),
+            TickSpacing::Auto => {
+                if is_primary {
+                    u16::min(5, max_auto_ticks)
+                } else {
+                    0
+                }
+            },
+            TickSpacing::None => 0,
+            // manual ticks are placed below, and between is rejected for major ticks
+            TickSpacing::Manual(_) | TickSpacing::Between(_) => 0,
+        };
+
+        // nice limits are rounded for the number of ticks actually drawn, so each tick is on a round step
+        let (span, limits) = if is_nice && nticks >= 2 {
+            let limits = nice_limits(span.0, span.1, nticks - 1);
+            (limits, limits)
+        } else if is_nice {
+            (limits, limits)
+        } else {
+            (span, limits)
         };
 
-        let is_primary = subplot.plot_infos.iter()
-            .any(|info| info.xaxis == placement || info.yaxis == placement)
-            | subplot.fill_infos.iter()
-            .any(|info| info.xaxis == placement || info.yaxis == placement);
+        // rounded limits get ticks on round steps within them, rather than spread across the data
+        let rounded_ticks = match (axis.limit_policy, subplot.format.round_limits) {
+            (Limits::Auto, Some(_)) => nice_ticks(limits, nticks),
+            _ => None,
+        };
+        let span = match &rounded_ticks {
+            Some(ticks) => (ticks[0], ticks[ticks.len() - 1]),
+            None => span,
+        };
 
         // get major tick marks
         let major_ticks = if let TickSpacing::Manual(ticks) = &axis.major_tick_marks {
             ticks.clone()
+        } else if let Some(ticks) = rounded_ticks {
+            ticks
         } else {
-            let nticks = match &axis.major_tick_marks {
-                TickSpacing::Count(n) => *n,
-                TickSpacing::On => 5,
-                TickSpacing::Auto => {
-                    if is_primary {
-                        5
-                    } else {
-                        0
-                    }
-                },
-                TickSpacing::None => 0,
-                _ => 0,
-            };
-
             (0..nticks)
                 .map(|n| span.0 + (span.1 - span.0) * (n as f64 / (nticks - 1) as f64))
                 .collect::<Vec<_>>()
@@ -574,6 +1144,17 @@ fn draw_subplot<B: backend::Canvas>(
         // get minor tick marks
         let minor_ticks = if let TickSpacing::Manual(ticks) = &axis.minor_tick_marks {
             ticks.clone()
+        } else if let TickSpacing::Between(n) = axis.minor_tick_marks {
+            // evenly subdivide the gap between each pair of neighboring major ticks
+            let mut sorted_major_ticks = major_ticks.clone();
+            sorted_major_ticks.sort_by(f64::total_cmp);
+
+            sorted_major_ticks.windows(2)
+                .flat_map(|pair| {
+                    let (low, high) = (pair[0], pair[1]);
+                    (1..=n).map(move |k| low + (high - low) * (k as f64 / (n + 1) as f64))
+                })
+                .collect::<Vec<_>>()
         } else {
             let nticks_per_major = match &axis.minor_tick_marks {
                 TickSpacing::Count(n) => *n,
@@ -586,7 +1167,8 @@ fn draw_subplot<B: backend::Canvas>(
                     }
                 },
                 TickSpacing::None => 0,
-                _ => 0,
+                // manual and between ticks are placed above
+                TickSpacing::Manual(_) | TickSpacing::Between(_) => 0,
             };
 
             if !major_ticks.is_empty() {
@@ -614,7 +1196,7 @@ fn draw_subplot<B: backend::Canvas>(
 
 
         // get major tick labels
-        let (major_labels, multiplier, offset) = match &axis.major_tick_labels {
+        let (mut major_labels, multiplier, offset) = match &axis.major_tick_labels {
             TickLabels::Manual(labels) => (labels.clone(), 0, 0.0),
             TickLabels::On => {
                 let modifiers = tick_modifiers(major_ticks.as_slice())?;
@@ -632,17 +1214,28 @@ fn draw_subplot<B: backend::Canvas>(
                 }
             },
         };
+        // hide labels at the ends of the axis
+        if subplot.format.trim_edge_labels && major_labels.len() == major_ticks.len() {
+            if let Some(label) = major_labels.first_mut() {
+                label.clear();
+            }
+            if let Some(label) = major_labels.last_mut() {
+                label.clear();
+            }
+        }
         // get minor tick labels
         let minor_labels = match &axis.minor_tick_labels {
             TickLabels::Manual(labels) => labels.clone(),
             TickLabels::On => {
                 let modifiers = tick_modifiers(major_ticks.as_slice())?; // use major modifiers
+                let modifiers = minor_tick_modifiers(minor_ticks.as_slice(), modifiers)?;
                 ticks_to_labels(minor_ticks.as_slice(), modifiers)?
             },
             TickLabels::None => vec![],
             TickLabels::Auto => {
                 if is_primary {
                     let modifiers = tick_modifiers(major_ticks.as_slice())?; // use major modifiers
+                    let modifiers = minor_tick_modifiers(minor_ticks.as_slice(), modifiers)?;
                     ticks_to_labels(minor_ticks.as_slice(), modifiers)?
                 } else {
                     vec![]
@@ -672,14 +1265,14 @@ fn draw_subplot<B: backend::Canvas>(
                 AxisType::X | AxisType::SecondaryX => letter_size.height,
             };
             *modifier_buffer.get_mut(&placement).unwrap() += tick_label_size;
-            *tick_buffer.get_mut(&placement).unwrap() += buffer_offset;
+            *tick_buffer.get_mut(&placement).unwrap() += tick_label_pad;
         } else if !minor_labels.is_empty() {
             let tick_label_size = match placement {
                 AxisType::Y | AxisType::SecondaryY => 5 * letter_size.width,
                 AxisType::X | AxisType::SecondaryX => letter_size.height,
             };
             *modifier_buffer.get_mut(&placement).unwrap() += tick_label_size;
-            *tick_buffer.get_mut(&placement).unwrap() += buffer_offset;
+            *tick_buffer.get_mut(&placement).unwrap() += tick_label_pad;
         }
 
         // add space for multiplier and offset if necessary
@@ -729,46 +1322,67 @@ fn draw_subplot<B: backend::Canvas>(
                 label_offset: offset,
                 major_grid,
                 minor_grid,
-                limits,
+                // swapped limits map values in the opposite direction
+                limits: if axis.inverted { (limits.1, limits.0) } else { limits },
                 visible: axis.visible,
+                line_color: axis.line_color.unwrap_or(line_color),
             },
         );
     }
 
     // add space for title
     let mut title_buffer = 0;
-    if !subplot.title.is_empty() {
+    if reserve_title_space || !subplot.title.trim().is_empty() {
         title_buffer += letter_size.height;
         *label_buffer.get_mut(&AxisType::SecondaryX).unwrap() += buffer_offset;
     }
 
+    // the buffers must leave at least one pixel for the plot area
+    let side_buffer = |placement: AxisType| {
+        subplot_buffer[&placement] + label_buffer[&placement] + modifier_buffer[&placement]
+            + tick_label_buffer[&placement] + tick_buffer[&placement]
+    };
+    let xbuffer = side_buffer(AxisType::Y) + side_buffer(AxisType::SecondaryY);
+    let ybuffer = side_buffer(AxisType::X) + side_buffer(AxisType::SecondaryX) + title_buffer;
+    if xbuffer >= subplot_area.xsize() || ybuffer >= subplot_area.ysize() {
+        return Err(PltError::SubplotTooSmall {
+            width: subplot_area.xsize(),
+            height: subplot_area.ysize(),
+        });
+    }
+
     // setup figure areas
 
-    let title_boundary = subplot_area.ymax - subplot_buffer[&AxisType::SecondaryX] - title_buffer;
+    // subtraction saturates, so oversized buffers collapse areas instead of wrapping around
+    let title_boundary = subplot_area.ymax
+        .saturating_sub(subplot_buffer[&AxisType::SecondaryX])
+        .saturating_sub(title_buffer);
 
     let label_boundary = draw::Area {
         xmin: subplot_area.xmin + subplot_buffer[&AxisType::Y] + label_buffer[&AxisType::Y],
-        xmax: subplot_area.xmax - subplot_buffer[&AxisType::SecondaryY] - label_buffer[&AxisType::SecondaryY],
+        xmax: subplot_area.xmax
+            .saturating_sub(subplot_buffer[&AxisType::SecondaryY])
+            .saturating_sub(label_buffer[&AxisType::SecondaryY]),
         ymin: subplot_area.ymin + subplot_buffer[&AxisType::X] + label_buffer[&AxisType::X],
-        ymax: title_boundary - label_buffer[&AxisType::SecondaryX],
+        ymax: title_boundary.saturating_sub(label_buffer[&AxisType::SecondaryX]),
     };
     let modifier_boundary = draw::Area {
         xmin: label_boundary.xmin + modifier_buffer[&AxisType::Y],
-        xmax: label_boundary.xmax - modifier_buffer[&AxisType::SecondaryY],
+        xmax: label_boundary.xmax.saturating_sub(modifier_buffer[&AxisType::SecondaryY]),
         ymin: label_boundary.ymin + modifier_buffer[&AxisType::X],
-        ymax: label_boundary.ymax - modifier_buffer[&AxisType::SecondaryX],
+        ymax: label_boundary.ymax.saturating_sub(modifier_buffer[&AxisType::SecondaryX]),
     };
     let tick_label_boundary = draw::Area {
         xmin: modifier_boundary.xmin + tick_label_buffer[&AxisType::Y],
-        xmax: modifier_boundary.xmax - tick_label_buffer[&AxisType::SecondaryY],
+        xmax: modifier_boundary.xmax.saturating_sub(tick_label_buffer[&AxisType::SecondaryY]),
         ymin: modifier_boundary.ymin + tick_label_buffer[&AxisType::X],
-        ymax: modifier_boundary.ymax - tick_label_buffer[&AxisType::SecondaryX],
+        ymax: modifier_boundary.ymax.saturating_sub(tick_label_buffer[&AxisType::SecondaryX]),
     };
     let tick_boundary = draw::Area {
         xmin: tick_label_boundary.xmin + tick_buffer[&AxisType::Y],
-        xmax: tick_label_boundary.xmax - tick_buffer[&AxisType::SecondaryY],
+        xmax: tick_label_boundary.xmax.saturating_sub(tick_buffer[&AxisType::SecondaryY]),
         ymin: tick_label_boundary.ymin + tick_buffer[&AxisType::X],
-        ymax: tick_label_boundary.ymax - tick_buffer[&AxisType::SecondaryX],
+        ymax: tick_label_boundary.ymax.saturating_sub(tick_buffer[&AxisType::SecondaryX]),
     };
 
     // plot area in figure as pixel indices
@@ -779,6 +1393,13 @@ fn draw_subplot<B: backend::Canvas>(
         ymax: tick_boundary.ymax,
     };
 
+    // nonplot lines and data are antialiased separately
+    canvas.set_antialias(subplot.format.antialias_nonplot)?;
+
+    if let GridLayer::BelowBackground = subplot.format.grid_layer {
+        draw_grid(canvas, &finalized_axes, &plot_area, line_width, grid_color)?;
+    }
+
     // set plot color
     canvas.draw_shape(draw::ShapeDescriptor {
         point: draw::Point {
@@ -794,58 +1415,12 @@ fn draw_subplot<B: backend::Canvas>(
         ..Default::default()
     })?;
 
-    // draw grid lines
-    for (placement, axis) in finalized_axes.iter() {
-        // draw ticks
-        for (ticks, grid) in [
-            (&axis.major_tick_locs, &axis.major_grid),
-            (&axis.minor_tick_locs, &axis.minor_grid),
-        ] {
-            // convert tick numbers to pixel locations
-            let tick_locs = ticks.iter()
-                // convert to fraction
-                .map(|tick| (tick - axis.limits.0) / (axis.limits.1 - axis.limits.0))
-                // convert to pixel
-                .map(|frac| plot_area.fractional_to_point(draw::Point { x: frac, y: frac }))
-                .collect::<Vec<_>>();
-
-            // draw grid lines
-            if *grid {
-                for loc in tick_locs.iter() {
-                    let line = match placement {
-                        AxisType::Y | AxisType::SecondaryY => draw::Line {
-                            p1: draw::Point {
-                                x: plot_area.xmin as f64,
-                                y: loc.y.round(),
-                            },
-                            p2: draw::Point {
-                                x: plot_area.xmax as f64,
-                                y: loc.y.round(),
-                            },
-                        },
-                        AxisType::X | AxisType::SecondaryX => draw::Line {
-                            p1: draw::Point {
-                                x: loc.x.round(),
-                                y: plot_area.ymin as f64,
-                            },
-                            p2: draw::Point {
-                                x: loc.x.round(),
-                                y: plot_area.ymax as f64,
-                            },
-                        },
-                    };
-                    canvas.draw_line(draw::LineDescriptor {
-                        line,
-                        line_color: grid_color,
-                        line_width,
-                        ..Default::default()
-                    })?;
-                }
-            }
-        }
+    if let GridLayer::BelowData = subplot.format.grid_layer {
+        draw_grid(canvas, &finalized_axes, &plot_area, line_width, grid_color)?;
     }
 
     // draw data
+    canvas.set_antialias(subplot.format.antialias)?;
 
     let mut plot_info_iter = subplot.plot_infos.iter();
     let mut fill_info_iter = subplot.fill_infos.iter();
@@ -856,34 +1431,98 @@ fn draw_subplot<B: backend::Canvas>(
     } else {
         vec![default_marker_color]
     };
-    let mut default_color = default_color.iter().cycle();
+    let ncolors = default_color.len();
+    let mut default_color = default_color.iter().cycle().enumerate();
 
     // if there is a color cycle, default to those colors, otherwise default to red for fill
     let default_fill_color = if !subplot.format.color_cycle.is_empty() {
-        subplot.format.color_cycle.iter().map(|&c| Color { a: 0.5, ..c }).collect()
+        subplot.format.color_cycle.iter().map(|&c| Color { a: band_alpha, ..c }).collect()
     } else {
         vec![default_fill_color]
     };
     let mut default_fill_color = default_fill_color.iter().cycle();
 
+    // resolve series colors ahead of drawing, so fills drawn first can still match them
+    let mut color_cycle = default_color.clone();
+    let series_colors = subplot.plot_infos.iter()
+        .map(|plot_info| {
+            let plot_info = plot_info.as_ref()?;
+            // colormapped series take no color from the cycle, as when drawn
+            let colormap_color = plot_info.colormap.as_ref().map(|colormap| colormap.color_at(0.0));
+            let line_color = plot_info.line.map(|line| {
+                colormap_color.or(line.color_override)
+                    .unwrap_or_else(|| *color_cycle.next().unwrap().1)
+            });
+            let marker_color = plot_info.marker.as_ref().map(|marker| {
+                colormap_color.or(marker.color_override)
+                    .unwrap_or_else(|| *color_cycle.next().unwrap().1)
+            });
+
+            line_color.or(marker_color)
+        })
+        .collect::<Vec<_>>();
+
+    // random numbers for jitter, seeded so renders are reproducible
+    let mut rng = SplitMix64(subplot.format.seed);
+
     // draw all data sets in the order called
     for plot_type in subplot.plot_order.iter() { match plot_type {
         // draw series data
         PlotType::Series => {
-            let plot_info = plot_info_iter.next().unwrap();
+            // skip removed series
+            let plot_info = match plot_info_iter.next().unwrap() {
+                Some(plot_info) => plot_info,
+                None => continue,
+            };
 
             let xlim = finalized_axes[&plot_info.xaxis].limits;
             let ylim = finalized_axes[&plot_info.yaxis].limits;
-            let plot_data = &plot_info.data;
+            // apply render-time jitter to x-values
+            let plot_data = plot_info.data.data()
+                .map(|(x, y)| if plot_info.jitter > 0.0 {
+                    (x + (rng.next_f64() - 0.5) * plot_info.jitter, y)
+                } else {
+                    (x, y)
+                })
+                .collect::<Vec<_>>();
+
+            // thin out long series, keeping the original index of each point and always the last point
+            // step data is left whole, as dropping points would break up its treads
+            let stride = match max_points {
+                Some(max) if plot_data.len() > max && !plot_info.data.is_step() => {
+                    usize::max((plot_data.len() + max - 1) / max.max(1), 1)
+                },
+                _ => 1,
+            };
+            let last_index = plot_data.len().saturating_sub(1);
+            let plot_data = plot_data.into_iter()
+                .enumerate()
+                .filter(|(index, _)| index % stride == 0 || *index == last_index)
+                .collect::<Vec<_>>();
+
+            // color used for clip indicators, taken from the line or markers
+            let mut series_color = None;
 
             // draw line
             if let Some(line) = plot_info.line {
-                let line_color = if let Some(color) = line.color_override {
-                    color
+                let (line_color, cycle_index) = if let Some(colormap) = &plot_info.colormap {
+                    (colormap.color_at(0.0), None)
+                } else if let Some(color) = line.color_override {
+                    (color, None)
                 } else {
-                    *default_color.next().unwrap()
+                    let (index, color) = default_color.next().unwrap();
+                    (*color, Some(index))
+                };
+                series_color = Some(line_color);
+                // solid lines colored from the cycle change style each time the color cycle wraps
+                let style_cycle = &subplot.format.style_cycle;
+                let line_style = match (cycle_index, line.style) {
+                    (Some(index), LineStyle::Solid) if !style_cycle.is_empty() => {
+                        style_cycle[(index / ncolors) % style_cycle.len()]
+                    },
+                    _ => line.style,
                 };
-                let dashes = match line.style {
+                let dashes = match line_style {
                     LineStyle::Solid => vec![],
                     LineStyle::Dashed => vec![
                         (10.0 * scaling).into(),
@@ -898,28 +1537,76 @@ fn draw_subplot<B: backend::Canvas>(
                         (4.0 * scaling).into(),
                     ],
                 };
-                canvas.draw_curve(draw::CurveDescriptor {
-                    points: plot_data.data()
-                        .map(|(x, y)| {
-                            let xfrac = (x - xlim.0) / (xlim.1 - xlim.0);
-                            let yfrac = (y - ylim.0) / (ylim.1 - ylim.0);
-
-                            let point = plot_area.fractional_to_point(draw::Point {
-                                x: xfrac,
-                                y: yfrac,
-                            });
-                            if plot_info.pixel_perfect {
-                                draw::Point { x: point.x.round(), y: point.y.round() }
-                            } else {
-                                point
-                            }
-                        })
-                        .collect::<Vec<_>>(),
-                    line_color,
-                    line_width: line.width * scaling.round() as u32,
-                    dashes: dashes.as_slice(),
-                    clip_area: Some(plot_area),
-                })?;
+                // optionally connect points in order of x
+                let mut line_data = plot_data.iter().map(|(_, point)| *point).collect::<Vec<_>>();
+                if plot_info.sort_x {
+                    line_data.sort_by(|a, b| a.0.total_cmp(&b.0));
+                }
+                let mut points = line_data.into_iter()
+                    .map(|(x, y)| {
+                        let xfrac = (x - xlim.0) / (xlim.1 - xlim.0);
+                        let yfrac = (y - ylim.0) / (ylim.1 - ylim.0);
+
+                        let point = plot_area.fractional_to_point(draw::Point {
+                            x: xfrac,
+                            y: yfrac,
+                        });
+                        if plot_info.pixel_perfect {
+                            draw::Point { x: point.x.round(), y: point.y.round() }
+                        } else {
+                            point
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                // connect back to the first point for closed shapes
+                if plot_info.closed && points.len() > 2 {
+                    points.push(points[0]);
+                }
+
+                // color of each segment, by its position along the line
+                let segment_color = |n: usize, nsegments: usize| match &plot_info.colormap {
+                    Some(colormap) => colormap.color_at(index_fraction(n, nsegments)),
+                    None => line_color,
+                };
+
+                if !plot_info.step_risers {
+                    // step points alternate between the start and end of each tread,
+                    // so even segments are treads and odd segments are hidden risers
+                    let ntreads = points.len() / 2;
+                    draw_segments(
+                        canvas,
+                        &points,
+                        |n| (n % 2 == 0).then_some(line.width * scaling.round() as u32),
+                        |n| segment_color(n / 2, ntreads),
+                        dashes.as_slice(),
+                        line.dash_offset * scaling as f64,
+                        plot_area,
+                    )?;
+                } else if plot_info.segmented
+                    || plot_info.line_widths.is_some()
+                    || plot_info.colormap.is_some()
+                {
+                    let line_widths = plot_info.line_widths.as_deref().unwrap_or(&[]);
+                    let nsegments = points.len().saturating_sub(1);
+                    draw_segments(
+                        canvas,
+                        &points,
+                        |n| Some(line_widths.get(n).copied().unwrap_or(line.width) * scaling.round() as u32),
+                        |n| segment_color(n, nsegments),
+                        dashes.as_slice(),
+                        line.dash_offset * scaling as f64,
+                        plot_area,
+                    )?;
+                } else {
+                    canvas.draw_curve(draw::CurveDescriptor {
+                        points,
+                        line_color,
+                        line_width: line.width * scaling.round() as u32,
+                        dashes: dashes.as_slice(),
+                        dash_offset: line.dash_offset * scaling as f64,
+                        clip_area: Some(plot_area),
+                    })?;
+                }
             }
 
             // draw markers
@@ -929,25 +1616,26 @@ fn draw_subplot<B: backend::Canvas>(
                     MarkerStyle::Square => draw::Shape::Square { l: marker.size },
                 };
                 shape.scale(scaling.round() as u32);
-                let fill_color = if let Some(color) = marker.color_override {
+                let fill_color = if let Some(colormap) = &plot_info.colormap {
+                    colormap.color_at(0.0)
+                } else if let Some(color) = marker.color_override {
                     color
                 } else {
-                    *default_color.next().unwrap()
+                    *default_color.next().unwrap().1
                 };
-                let line = if marker.outline {
+                series_color.get_or_insert(fill_color);
+                let line = if marker.outline || marker.hollow {
                     marker.outline_format
                 } else {
                     Line {
                         style: LineStyle::Solid,
                         width: Line::default().width,
                         color_override: Some(Color::TRANSPARENT),
+                        dash_offset: 0.0,
                     }
                 };
-                let line_color = if let Some(color) = line.color_override {
-                    color
-                } else {
-                    fill_color
-                };
+                let line_color = line.color_override;
+                let hollow = marker.hollow;
                 let line_dashes = match line.style {
                     LineStyle::Solid => vec![],
                     LineStyle::Dashed => vec![
@@ -963,7 +1651,18 @@ fn draw_subplot<B: backend::Canvas>(
                         (4.0 * scaling).into(),
                     ],
                 };
-                for point in plot_data.data().map(|(x, y)| {
+                // optionally only mark selected points
+                let marked_data = plot_data.iter()
+                    .filter(|(index, _)| match &plot_info.marker_indices {
+                        Some(indices) => indices.contains(index),
+                        None => true,
+                    })
+                    .map(|(index, point)| (*index, *point));
+                // grid cells already holding a marker, when skipping overlapping markers
+                // a cell is one marker radius across, so markers sharing one mostly overlap
+                let cell_size = f64::max((marker.size * scaling.round() as u32) as f64, 1.0);
+                let mut marked_cells = HashSet::new();
+                for (index, point) in marked_data.map(|(index, (x, y))| {
                     let xfrac = (x - xlim.0) / (xlim.1 - xlim.0);
                     let yfrac = (y - ylim.0) / (ylim.1 - ylim.0);
 
@@ -972,23 +1671,69 @@ fn draw_subplot<B: backend::Canvas>(
                         y: yfrac,
                     });
 
-                    if plot_info.pixel_perfect {
+                    let point = if plot_info.pixel_perfect {
                         draw::Point { x: point.x.round(), y: point.y.round() }
                     } else {
                         point
-                    }
+                    };
+
+                    (index, point)
                 }) {
+                    if plot_info.dedupe_markers
+                        && !marked_cells.insert((
+                            (point.x / cell_size).floor() as i64,
+                            (point.y / cell_size).floor() as i64,
+                        ))
+                    {
+                        continue;
+                    }
+
+                    let fill_color = match &plot_info.colormap {
+                        Some(colormap) => colormap.color_at(index_fraction(index, last_index + 1)),
+                        None => fill_color,
+                    };
                     canvas.draw_shape(draw::ShapeDescriptor {
                         point,
                         shape,
-                        fill_color,
-                        line_color,
+                        fill_color: if hollow { Color::TRANSPARENT } else { fill_color },
+                        line_color: line_color.unwrap_or(fill_color),
                         line_width: line.width * scaling.round() as u32,
                         line_dashes: line_dashes.as_slice(),
                         clip_area: Some(plot_area),
                     })?;
                 }
             }
+
+            // draw indicators where data extends beyond the limits
+            if subplot.format.clip_indicators {
+                let color = series_color.unwrap_or(default_marker_color);
+                let size = 6.0 * scaling as f64;
+
+                let mut last_point = None;
+                for &(_, (x, y)) in plot_data.iter() {
+                    let frac = draw::Point {
+                        x: (x - xlim.0) / (xlim.1 - xlim.0),
+                        y: (y - ylim.0) / (ylim.1 - ylim.0),
+                    };
+                    if (0.0..=1.0).contains(&frac.x) && (0.0..=1.0).contains(&frac.y) {
+                        last_point = None;
+                        continue;
+                    }
+
+                    // only draw one indicator per edge location
+                    let point = plot_area.fractional_to_point(draw::Point {
+                        x: frac.x.clamp(0.0, 1.0),
+                        y: frac.y.clamp(0.0, 1.0),
+                    });
+                    let point = draw::Point { x: point.x.round(), y: point.y.round() };
+                    if last_point == Some((point.x, point.y)) {
+                        continue;
+                    }
+                    last_point = Some((point.x, point.y));
+
+                    draw_clip_indicator(canvas, &plot_area, frac, point, size, color)?;
+                }
+            }
         }
         // draw fill data
         PlotType::Fill => {
@@ -997,35 +1742,71 @@ fn draw_subplot<B: backend::Canvas>(
             let xlim = finalized_axes[&fill_info.xaxis].limits;
             let ylim = finalized_axes[&fill_info.yaxis].limits;
             //let color = fill_info.color;
+            let matched_color = fill_info.matched_series
+                .and_then(|handle| series_colors.get(handle.0).copied().flatten());
             let color = if let Some(color) = fill_info.color_override {
                 color
+            } else if let Some(color) = matched_color {
+                Color { a: band_alpha, ..color }
             } else {
                 *default_fill_color.next().unwrap()
             };
             let data = &fill_info.data;
 
-            let shape_points: Vec<_> = Iterator::chain(data.curve1(), data.curve2().rev())
-                .map(|(x, y)| {
-                    let xfrac = (x - xlim.0) / (xlim.1 - xlim.0);
-                    let yfrac = (y - ylim.0) / (ylim.1 - ylim.0);
+            let curve1 = data.curve1().collect::<Vec<_>>();
+            let curve2 = data.curve2().collect::<Vec<_>>();
 
-                    plot_area.fractional_to_point(draw::Point {
-                        x: xfrac,
-                        y: yfrac,
+            for polygon in fill_polygons(&curve1, &curve2) {
+                let shape_points: Vec<_> = polygon.into_iter()
+                    .map(|(x, y)| {
+                        let xfrac = (x - xlim.0) / (xlim.1 - xlim.0);
+                        let yfrac = (y - ylim.0) / (ylim.1 - ylim.0);
+
+                        plot_area.fractional_to_point(draw::Point {
+                            x: xfrac,
+                            y: yfrac,
+                        })
                     })
-                })
-                .collect();
+                    .collect();
 
-            canvas.fill_region(draw::FillDescriptor {
-                points: shape_points,
-                fill_color: color,
-                clip_area: Some(plot_area),
-            })?;
+                canvas.fill_region(draw::FillDescriptor {
+                    points: shape_points,
+                    fill_color: color,
+                    clip_area: Some(plot_area),
+                })?;
+            }
         }
     }}
 
+    canvas.set_antialias(subplot.format.antialias_nonplot)?;
+
+    if let GridLayer::AboveData = subplot.format.grid_layer {
+        draw_grid(canvas, &finalized_axes, &plot_area, line_width, grid_color)?;
+    }
+
+    // draw custom overlays, antialiased like data
+    canvas.set_antialias(subplot.format.antialias)?;
+    let limits = finalized_axes.iter()
+        .map(|(&placement, axis)| (placement, axis.limits))
+        .collect::<BTreeMap<_, _>>();
+    for overlay_fn in subplot.overlays.iter() {
+        let mut overlay = Overlay::new(plot_area, limits.clone());
+        (overlay_fn.0)(&mut overlay);
+        overlay.draw(canvas)?;
+    }
+
+    canvas.set_antialias(subplot.format.antialias_nonplot)?;
+
     // draw axis lines, labels, ticks, and tick labels for each axis
+    // these are drawn after all data, so thick lines at the plot edge never cover the axes
     for (placement, axis) in finalized_axes {
+        let (
+            inner_major_tick_length,
+            outer_major_tick_length,
+            inner_minor_tick_length,
+            outer_minor_tick_length,
+        ) = tick_lengths(placement);
+
         // get line placement
         let axis_offset = line_width as f64 / 2.0;
         let line = match placement {
@@ -1072,7 +1853,7 @@ fn draw_subplot<B: backend::Canvas>(
         };
 
         let axis_line_color = if axis.visible {
-            line_color
+            axis.line_color
         } else {
             Color::TRANSPARENT
         };
@@ -1154,11 +1935,10 @@ fn draw_subplot<B: backend::Canvas>(
                     y: (plot_area.ymax + plot_area.ymin) as f64 / 2.0,
                 },
                 alignment: draw::Alignment::Right,
-                rotation: 1.5 * f64::consts::PI,
                 color: font_color,
                 font: label_font,
                 ..Default::default()
-            })?,
+            }.with_rotation_degrees(270.0))?,
             AxisType::X => canvas.draw_text(draw::TextDescriptor {
                 text: axis.label,
                 position: draw::Point {
@@ -1178,11 +1958,10 @@ fn draw_subplot<B: backend::Canvas>(
                     y: (plot_area.ymax + plot_area.ymin) as f64 / 2.0,
                 },
                 alignment: draw::Alignment::Left,
-                rotation: 0.5 * f64::consts::PI,
                 color: font_color,
                 font: label_font,
                 ..Default::default()
-            })?,
+            }.with_rotation_degrees(90.0))?,
             AxisType::SecondaryX => canvas.draw_text(draw::TextDescriptor {
                 text: axis.label,
                 position: draw::Point {
@@ -1216,15 +1995,9 @@ fn draw_subplot<B: backend::Canvas>(
             let labels = if labels.is_empty() {
                 (0..ticks.len()).map(|_| String::new()).collect()
             } else if labels.len() != ticks.len() {
-                let axis = match placement {
-                    AxisType::Y => "y-axis",
-                    AxisType::X => "x-axis",
-                    AxisType::SecondaryY => "secondary y-axis",
-                    AxisType::SecondaryX => "secondary x-axis",
-                };
                 return Err(PltError::BadTickLabels(format!(
                     "number of tick labels does not match number of ticks on {}",
-                    axis,
+                    placement.name(),
                 )));
             } else {
                 labels
@@ -1245,7 +2018,7 @@ fn draw_subplot<B: backend::Canvas>(
                     AxisType::Y => (
                         draw::Line {
                             p1: draw::Point {
-                                x: (plot_area.xmin - outer_tick_length) as f64,
+                                x: plot_area.xmin.saturating_sub(outer_tick_length) as f64,
                                 y: loc.y.round(),
                             },
                             p2: draw::Point {
@@ -1257,13 +2030,13 @@ fn draw_subplot<B: backend::Canvas>(
                             x: tick_label_boundary.xmin as f64,
                             y: loc.y.round(),
                         },
-                        draw::Alignment::Right,
+                        draw::Alignment::MiddleRight,
                     ),
                     AxisType::X => (
                         draw::Line {
                             p1: draw::Point {
                                 x: loc.x.round(),
-                                y: (plot_area.ymin - outer_tick_length) as f64,
+                                y: plot_area.ymin.saturating_sub(outer_tick_length) as f64,
                             },
                             p2: draw::Point {
                                 x: loc.x.round(),
@@ -1279,7 +2052,7 @@ fn draw_subplot<B: backend::Canvas>(
                     AxisType::SecondaryY => (
                         draw::Line {
                             p1: draw::Point {
-                                x: (plot_area.xmax - inner_tick_length) as f64,
+                                x: plot_area.xmax.saturating_sub(inner_tick_length) as f64,
                                 y: loc.y.round(),
                             },
                             p2: draw::Point {
@@ -1291,13 +2064,13 @@ fn draw_subplot<B: backend::Canvas>(
                             x: tick_label_boundary.xmax as f64,
                             y: loc.y.round(),
                         },
-                        draw::Alignment::Left,
+                        draw::Alignment::MiddleLeft,
                     ),
                     AxisType::SecondaryX => (
                         draw::Line {
                             p1: draw::Point {
                                 x: loc.x.round(),
-                                y: (plot_area.ymax - inner_tick_length) as f64,
+                                y: plot_area.ymax.saturating_sub(inner_tick_length) as f64,
                             },
                             p2: draw::Point {
                                 x: loc.x.round(),
@@ -1315,7 +2088,7 @@ fn draw_subplot<B: backend::Canvas>(
                 // draw line and text
                 canvas.draw_line(draw::LineDescriptor {
                     line: tick_line,
-                    line_color,
+                    line_color: axis.line_color,
                     line_width,
                     ..Default::default()
                 })?;
@@ -1352,5 +2125,266 @@ fn draw_subplot<B: backend::Canvas>(
         ..Default::default()
     })?;
 
+    // draw border around the whole subplot area
+    if let Some(border) = subplot.format.cell_border {
+        let width = border.width * scaling.round() as u32;
+        let dashes = match border.style {
+            LineStyle::Solid => vec![],
+            LineStyle::Dashed => vec![
+                (10.0 * scaling).into(),
+                (10.0 * scaling).into(),
+                (10.0 * scaling).into(),
+                (10.0 * scaling).into(),
+            ],
+            LineStyle::ShortDashed => vec![
+                (4.0 * scaling).into(),
+                (4.0 * scaling).into(),
+                (4.0 * scaling).into(),
+                (4.0 * scaling).into(),
+            ],
+        };
+
+        // inset by half the line width, so the border stays inside the subplot area
+        canvas.draw_shape(draw::ShapeDescriptor {
+            point: draw::Point {
+                x: subplot_area.xmin as f64 + subplot_area.xsize() as f64 / 2.0,
+                y: subplot_area.ymin as f64 + subplot_area.ysize() as f64 / 2.0,
+            },
+            shape: draw::Shape::Rectangle {
+                h: subplot_area.ysize().saturating_sub(width),
+                w: subplot_area.xsize().saturating_sub(width),
+            },
+            fill_color: Color::TRANSPARENT,
+            line_width: width,
+            line_color: border.color,
+            line_dashes: dashes.as_slice(),
+            clip_area: None,
+        })?;
+    }
+
     Ok(())
 }
+
+#[cfg(all(test, feature = "cairo"))]
+mod tests {
+    use super::*;
+    use crate::{Axes, SingleLayout, Subplot};
+    use draw_cairo::cairo;
+
+    /// A figure holding a single subplot.
+    fn single_figure(subplot: Subplot) -> Figure {
+        let mut figure = <Figure>::default();
+        figure.set_layout(SingleLayout::new(subplot)).unwrap();
+
+        figure
+    }
+
+    /// A figure with one subplot of a plotted line.
+    fn line_figure<'a>() -> Figure<'a> {
+        let mut subplot = Subplot::builder().build();
+        subplot.plot(vec![0.0, 1.0, 2.0], vec![0.0, 1.0, 4.0]).unwrap();
+
+        single_figure(subplot)
+    }
+
+    /// Draws a figure onto an image surface, returning the color of each pixel, by rows from the top.
+    fn render(figure: &Figure) -> Vec<Vec<[u8; 3]>> {
+        let (width, height) = (figure.size.width, figure.size.height);
+        let surface = cairo::ImageSurface::create(cairo::Format::ARgb32, width as i32, height as i32)
+            .unwrap();
+        {
+            let context = cairo::Context::new(&surface).unwrap();
+            figure.draw_to_cairo(&context, draw::Area { xmin: 0, xmax: width, ymin: 0, ymax: height })
+                .unwrap();
+        }
+
+        pixels(surface)
+    }
+
+    /// Returns the color of each pixel of a surface no longer drawn to, by rows from the top.
+    fn pixels(mut surface: cairo::ImageSurface) -> Vec<Vec<[u8; 3]>> {
+        surface.flush();
+
+        let (width, height) = (surface.width() as usize, surface.height() as usize);
+        let stride = surface.stride() as usize;
+        let data = surface.data().unwrap();
+        (0..height)
+            .map(|row| (0..width)
+                .map(|col| {
+                    // cairo stores native endian ARGB words
+                    let offset = row * stride + col * 4;
+                    let argb = u32::from_ne_bytes(data[offset..offset + 4].try_into().unwrap());
+                    [(argb >> 16) as u8, (argb >> 8) as u8, argb as u8]
+                })
+                .collect())
+            .collect()
+    }
+
+    fn is_white([r, g, b]: [u8; 3]) -> bool {
+        r > 250 && g > 250 && b > 250
+    }
+
+    fn is_red([r, g, b]: [u8; 3]) -> bool {
+        r > 200 && g < 100 && b < 100
+    }
+
+    #[test]
+    #[cfg(feature = "png")]
+    fn draw_file_to_missing_directory_is_err() {
+        let filename = std::env::temp_dir().join("plt-missing-directory").join("figure.png");
+
+        assert!(line_figure().draw_file(FileFormat::Png, filename).is_err());
+    }
+
+    #[test]
+    fn axes_are_drawn_over_data() {
+        let mut subplot = Subplot::builder()
+            .limits(Axes::BothX, Limits::Manual { min: 0.25, max: 0.75 })
+            .limits(Axes::BothY, Limits::Manual { min: 0.0, max: 1.0 })
+            .major_tick_marks(Axes::All, TickSpacing::None)
+            .minor_tick_marks(Axes::All, TickSpacing::None)
+            .major_tick_labels(Axes::All, TickLabels::None)
+            .build();
+        // a thick line running off both sides of the plot area
+        subplot.plotter()
+            .line_width(12)
+            .line_color(Color::RED)
+            .plot(vec![0.0, 1.0], vec![0.5, 0.5])
+            .unwrap();
+
+        let pixels = render(&single_figure(subplot));
+        let middle = pixels[0].len() / 2;
+        let red_rows = (0..pixels.len()).filter(|&row| is_red(pixels[row][middle])).collect::<Vec<_>>();
+        assert!(!red_rows.is_empty());
+
+        // only the side axes are drawn on a row clear of the line
+        let clear_row = red_rows[0] - 40;
+        let axis_columns = (0..pixels[0].len())
+            .filter(|&col| !is_white(pixels[clear_row][col]))
+            .collect::<Vec<_>>();
+        assert!(!axis_columns.is_empty());
+
+        let line_row = red_rows[red_rows.len() / 2];
+        for col in axis_columns {
+            assert!(!is_red(pixels[line_row][col]), "line drawn over axis at column {}", col);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "png")]
+    fn drawing_is_reproducible() {
+        let figure = line_figure();
+
+        let mut first = vec![];
+        figure.write_to(&mut first, FileFormat::Png).unwrap();
+        let mut second = vec![];
+        figure.write_to(&mut second, FileFormat::Png).unwrap();
+
+        assert!(!first.is_empty());
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn draw_to_backend_paints_over_previous_frame() {
+        let size = <Figure>::default().size;
+        let surface = cairo::ImageSurface::create(cairo::Format::ARgb32, size.width as i32, size.height as i32)
+            .unwrap();
+        {
+            let context = cairo::Context::new(&surface).unwrap();
+            let mut canvas = backend::CairoCanvas::from_context(&context, size, draw::ImageFormat::Bitmap);
+
+            let mut subplot = Subplot::builder().build();
+            subplot.plotter()
+                .line_width(12)
+                .line_color(Color::RED)
+                .plot(vec![0.0, 1.0], vec![0.0, 1.0])
+                .unwrap();
+            single_figure(subplot).draw_to_backend(&mut canvas).unwrap();
+
+            // the next frame has no data
+            single_figure(Subplot::builder().build()).draw_to_backend(&mut canvas).unwrap();
+        }
+
+        assert!(pixels(surface).iter().flatten().all(|&pixel| !is_red(pixel)));
+    }
+
+    #[test]
+    fn auto_ticks_are_left_off_unused_axes() {
+        let mut subplot = Subplot::builder()
+            .major_tick_marks(Axes::SecondaryY, TickSpacing::Auto)
+            .minor_tick_marks(Axes::SecondaryY, TickSpacing::Auto)
+            .major_tick_labels(Axes::SecondaryY, TickLabels::Auto)
+            .build();
+        subplot.plot(vec![0.0, 1.0], vec![0.0, 1.0]).unwrap();
+
+        let pixels = render(&single_figure(subplot));
+        let rightmost_drawn = |row: &Vec<[u8; 3]>| row.iter().rposition(|&pixel| !is_white(pixel));
+
+        // nothing is drawn right of the secondary y-axis line
+        let axis_column = rightmost_drawn(&pixels[pixels.len() / 2]).unwrap();
+        for row in pixels.iter() {
+            assert!(!matches!(rightmost_drawn(row), Some(col) if col > axis_column + 1));
+        }
+    }
+
+    #[test]
+    fn tiny_figure_is_err() {
+        let format = FigureFormat { size: FigSize::from_pixels(20, 20, 100), ..Default::default() };
+        let mut figure = <Figure>::new(&format);
+        let mut subplot = Subplot::builder().build();
+        subplot.plot(vec![0.0, 1.0], vec![0.0, 1.0]).unwrap();
+        figure.set_layout(SingleLayout::new(subplot)).unwrap();
+
+        let surface = cairo::ImageSurface::create(cairo::Format::ARgb32, 20, 20).unwrap();
+        let context = cairo::Context::new(&surface).unwrap();
+        let result = figure.draw_to_cairo(&context, draw::Area { xmin: 0, xmax: 20, ymin: 0, ymax: 20 });
+
+        assert!(matches!(result, Err(PltError::SubplotTooSmall { .. })), "{:?}", result);
+    }
+
+    #[test]
+    fn oversized_font_on_small_figure_is_err() {
+        let format = FigureFormat { size: FigSize::from_pixels(200, 150, 100), ..Default::default() };
+        let mut figure = <Figure>::new(&format);
+        let mut subplot = Subplot::builder()
+            .format(SubplotFormat { font_size: 100.0, ..Default::default() })
+            .title("title")
+            .label(Axes::BothX, "x")
+            .label(Axes::BothY, "y")
+            .build();
+        subplot.plot(vec![0.0, 1.0], vec![0.0, 1.0]).unwrap();
+        figure.set_layout(SingleLayout::new(subplot)).unwrap();
+
+        let surface = cairo::ImageSurface::create(cairo::Format::ARgb32, 200, 150).unwrap();
+        let context = cairo::Context::new(&surface).unwrap();
+        let result = figure.draw_to_cairo(&context, draw::Area { xmin: 0, xmax: 200, ymin: 0, ymax: 150 });
+
+        assert!(matches!(result, Err(PltError::SubplotTooSmall { .. })), "{:?}", result);
+    }
+
+    #[test]
+    fn step_riser_fills_one_pixel_column() {
+        let mut subplot = Subplot::builder()
+            .limits(Axes::BothX, Limits::Manual { min: 0.0, max: 1.0 })
+            .limits(Axes::BothY, Limits::Manual { min: 0.0, max: 1.0 })
+            .major_tick_marks(Axes::All, TickSpacing::None)
+            .minor_tick_marks(Axes::All, TickSpacing::None)
+            .major_tick_labels(Axes::All, TickLabels::None)
+            .build();
+        // a single riser at x = 0.3, which does not fall on a whole pixel
+        subplot.plotter()
+            .line_width(1)
+            .line_color(Color::RED)
+            .step(vec![0.0, 0.3, 1.0], vec![0.25, 0.75])
+            .unwrap();
+
+        let pixels = render(&single_figure(subplot));
+        // the middle row only crosses the riser, any tint of red is from it
+        let row = &pixels[pixels.len() / 2];
+        let riser_columns = row.iter().filter(|&&[r, g, _]| r > g.saturating_add(10)).collect::<Vec<_>>();
+
+        assert_eq!(riser_columns.len(), 1, "riser spans {:?}", riser_columns);
+        assert!(is_red(*riser_columns[0]));
+    }
+}
+
@@ -1,6 +1,8 @@
 use crate::subplot::{Subplot, SubplotDescriptor};
 use crate::PltError;
 
+use std::iter;
+
 #[cfg(doc)]
 use crate::figure::Figure;
 
@@ -33,27 +35,17 @@ pub struct GridLayout<'a> {
     subplots: ndarray::Array2<Subplot<'a>>,
     areas: ndarray::Array2<FractionalArea>,
     mask: ndarray::Array2<bool>,
+    row_ratios: Vec<f64>,
+    col_ratios: Vec<f64>,
+    hspace: f64,
+    wspace: f64,
 }
 impl<'a> GridLayout<'a> {
     /// Creates an empty layout.
     pub fn new(nrows: usize, ncols: usize) -> Self {
-        let xextent = 1.0 / ncols as f64;
-        let yextent = 1.0 / nrows as f64;
-        let areas = (0..(nrows * ncols))
-            .map(|index| {
-                // get row and column indices
-                let row = index / ncols;
-                let col = index % ncols;
-
-                let xmin = xextent * col as f64;
-                let xmax = xmin + xextent;
-                let ymin = yextent * (nrows - 1 - row) as f64;
-                let ymax = ymin + yextent;
-
-                FractionalArea { xmin, xmax, ymin, ymax }
-            })
-            .collect::<ndarray::Array1<_>>();
-        let areas = areas.into_shape((nrows, ncols)).unwrap();
+        let row_ratios = vec![1.0; nrows];
+        let col_ratios = vec![1.0; ncols];
+        let areas = grid_areas(&row_ratios, &col_ratios, 0.0, 0.0);
 
         Self {
             subplots: ndarray::Array2::from_elem(
@@ -62,6 +54,10 @@ impl<'a> GridLayout<'a> {
             ),
             areas,
             mask: ndarray::Array2::from_elem((nrows, ncols), false),
+            row_ratios,
+            col_ratios,
+            hspace: 0.0,
+            wspace: 0.0,
         }
     }
     /// Creates a uniform grid layout from a 2D array, filling only the spots with [`Some`] subplot.
@@ -71,23 +67,9 @@ impl<'a> GridLayout<'a> {
         let nrows = subplots.nrows();
         let ncols = subplots.ncols();
 
-        let xextent = 1.0 / ncols as f64;
-        let yextent = 1.0 / nrows as f64;
-        let areas = (0..(nrows * ncols))
-            .map(|index| {
-                // get row and column indices
-                let row = index / ncols;
-                let col = index % ncols;
-
-                let xmin = xextent * col as f64;
-                let xmax = xmin + xextent;
-                let ymin = yextent * (nrows - 1 - row) as f64;
-                let ymax = ymin + yextent;
-
-                FractionalArea { xmin, xmax, ymin, ymax }
-            })
-            .collect::<ndarray::Array1<_>>();
-        let areas = areas.into_shape((nrows, ncols)).unwrap();
+        let row_ratios = vec![1.0; nrows];
+        let col_ratios = vec![1.0; ncols];
+        let areas = grid_areas(&row_ratios, &col_ratios, 0.0, 0.0);
 
         let mask = subplots.map(|subplot| subplot.is_some());
         let subplots = subplots.mapv(|subplot| {
@@ -98,7 +80,55 @@ impl<'a> GridLayout<'a> {
             subplots,
             areas,
             mask,
+            row_ratios,
+            col_ratios,
+            hspace: 0.0,
+            wspace: 0.0,
+        }
+    }
+    /// Sets the relative heights of rows and widths of columns, replacing the default equal division.
+    pub fn set_ratios(&mut self, row_ratios: &[f64], col_ratios: &[f64]) -> Result<(), PltError> {
+        if row_ratios.len() != self.subplots.nrows() {
+            return Err(PltError::InvalidData(format!(
+                "expected {} row ratios, got {}",
+                self.subplots.nrows(),
+                row_ratios.len(),
+            )));
+        }
+        if col_ratios.len() != self.subplots.ncols() {
+            return Err(PltError::InvalidData(format!(
+                "expected {} column ratios, got {}",
+                self.subplots.ncols(),
+                col_ratios.len(),
+            )));
+        }
+        if row_ratios.iter().chain(col_ratios).any(|ratio| !(ratio.is_finite() && *ratio > 0.0)) {
+            return Err(PltError::InvalidData("grid ratios must be finite and positive".to_owned()));
+        }
+
+        self.row_ratios = row_ratios.to_vec();
+        self.col_ratios = col_ratios.to_vec();
+        self.areas = grid_areas(&self.row_ratios, &self.col_ratios, self.hspace, self.wspace);
+
+        Ok(())
+    }
+    /// Sets the gaps between rows (`hspace`) and columns (`wspace`), as fractions of the figure size.
+    pub fn set_spacing(&mut self, hspace: f64, wspace: f64) -> Result<(), PltError> {
+        let nrows = self.subplots.nrows();
+        let ncols = self.subplots.ncols();
+
+        if !(hspace.is_finite() && hspace >= 0.0 && wspace.is_finite() && wspace >= 0.0) {
+            return Err(PltError::InvalidData("grid spacing must be finite and non-negative".to_owned()));
+        }
+        if hspace * nrows.saturating_sub(1) as f64 >= 1.0 || wspace * ncols.saturating_sub(1) as f64 >= 1.0 {
+            return Err(PltError::InvalidData("grid spacing leaves no room for subplots".to_owned()));
         }
+
+        self.hspace = hspace;
+        self.wspace = wspace;
+        self.areas = grid_areas(&self.row_ratios, &self.col_ratios, self.hspace, self.wspace);
+
+        Ok(())
     }
     /// Adds or replaces a subplot at the specified location.
     pub fn insert(
@@ -132,6 +162,52 @@ impl<'a> Layout<'a> for GridLayout<'a> {
     }
 }
 
+/// Divides the figure into grid cells sized by relative row and column ratios, separated by gaps.
+fn grid_areas(
+    row_ratios: &[f64],
+    col_ratios: &[f64],
+    hspace: f64,
+    wspace: f64,
+) -> ndarray::Array2<FractionalArea> {
+    let row_edges = ratio_edges(row_ratios);
+    let col_edges = ratio_edges(col_ratios);
+
+    // fraction of the figure left for subplots after gaps
+    let height = 1.0 - hspace * row_ratios.len().saturating_sub(1) as f64;
+    let width = 1.0 - wspace * col_ratios.len().saturating_sub(1) as f64;
+
+    // rows are counted from the top of the figure
+    ndarray::Array2::from_shape_fn((row_ratios.len(), col_ratios.len()), |(row, col)| {
+        let xoffset = wspace * col as f64;
+        let yoffset = hspace * row as f64;
+
+        FractionalArea {
+            xmin: xoffset + width * col_edges[col],
+            xmax: f64::min(xoffset + width * col_edges[col + 1], 1.0),
+            ymin: f64::max(1.0 - (yoffset + height * row_edges[row + 1]), 0.0),
+            ymax: 1.0 - (yoffset + height * row_edges[row]),
+        }
+    })
+}
+
+/// Cumulative fractional boundaries of cells with the provided relative sizes.
+fn ratio_edges(ratios: &[f64]) -> Vec<f64> {
+    let total = ratios.iter().sum::<f64>();
+    let mut edges = iter::once(0.0)
+        .chain(ratios.iter().scan(0.0, |sum, ratio| {
+            *sum += ratio;
+            Some(*sum / total)
+        }))
+        .collect::<Vec<_>>();
+
+    // avoid rounding error pushing the last edge outside the figure
+    if let Some(last) = edges.last_mut() {
+        *last = 1.0;
+    }
+
+    edges
+}
+
 /// Defines an area of a figure in terms of fractional boundaries.
 #[derive(Copy, Clone, Debug)]
 pub struct FractionalArea {
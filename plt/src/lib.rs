@@ -45,20 +45,27 @@
 
 mod figure;
 mod layout;
+mod overlay;
 mod subplot;
 
 // bring pub elements from submodules into main lib module
 pub use figure::*;
 pub use layout::*;
+pub use overlay::*;
 pub use subplot::*;
 
 // re-export necessary elements from plt-draw
-pub use draw::{Color, FileFormat, FontName};
+pub use draw::{Color, FileFormat, FontName, Size};
 
 // re-export backend canvas in separate module
 /// Re-exports of neccessary plt-draw backend elements.
 pub mod backend {
     pub use draw::Canvas;
+    // drawing primitives, for custom drawing on an Overlay
+    pub use draw::{
+        Alignment, Area, CurveDescriptor, FillDescriptor, Font, FontSlant, FontWeight, Line,
+        LineDescriptor, Point, Shape, ShapeDescriptor, TextDescriptor,
+    };
     #[cfg(feature = "cairo")]
     pub use draw_cairo::CairoCanvas;
 }
@@ -70,6 +77,9 @@ pub enum PltError {
     /// Returned in the case of input data in an invalid state.
     #[error("Input data is in an invalid state: `{0}`")]
     InvalidData(String),
+    /// Returned when a subplot format is not drawable.
+    #[error("Subplot format is invalid: `{0}`")]
+    InvalidFormat(String),
     /// Returned in the case of a subplot index that is out of bounds.
     #[error("index `{index}` is out of range for figure with {nrows} rows and {ncols} columns")]
     InvalidIndex { index: u32, nrows: u32, ncols: u32 },
@@ -79,6 +89,9 @@ pub enum PltError {
     /// Returned in the case of a subplot index that is out of bounds.
     #[error("column index `{col}` is out of range for layout with {ncols} columns")]
     InvalidColumn { col: usize, ncols: usize },
+    /// Returned when a series handle does not refer to a plotted series.
+    #[error("no series with index `{0}` in subplot")]
+    InvalidSeries(usize),
     /// Returned when tick mark locations has an unusable value.
     #[error("one or more ticks have invalid locations: `{0}`")]
     BadTickPlacement(String),
@@ -88,6 +101,9 @@ pub enum PltError {
     /// Returned when the provided area of a subplot is not valid.
     #[error("{0:?} is not a valid fractional area")]
     InvalidSubplotArea(layout::FractionalArea),
+    /// Returned when a subplot is too small to fit its labels, ticks and plot area.
+    #[error("subplot of {width}x{height} pixels is too small to fit its labels and ticks")]
+    SubplotTooSmall { width: u32, height: u32 },
     /// Returned when the drawing backend returns an error.
     #[error(transparent)]
     DrawError(#[from] draw::DrawError)
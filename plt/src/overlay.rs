@@ -0,0 +1,116 @@
+use crate::backend;
+use crate::subplot::AxisType;
+use crate::PltError;
+
+use std::{collections::BTreeMap, fmt, rc::Rc};
+
+/// Custom drawing on a subplot, added with [`Subplot::on_draw`].
+///
+/// Drawing is recorded and then drawn on the figure backend after plotted data, beneath the axes.
+/// Positions are in figure pixels, as with [`Overlay::plot_area`].
+/// Use [`Overlay::data_to_point`] to place drawing at data coordinates.
+///
+/// [`Subplot::on_draw`]: crate::Subplot::on_draw
+#[derive(Debug)]
+pub struct Overlay<'a> {
+    plot_area: draw::Area,
+    limits: BTreeMap<AxisType, (f64, f64)>,
+    commands: Vec<DrawCommand<'a>>,
+}
+impl<'a> Overlay<'a> {
+    /// The area of the figure, in pixels, inside the subplot axes.
+    pub fn plot_area(&self) -> draw::Area {
+        self.plot_area
+    }
+
+    /// The limits of an axis, as drawn.
+    /// Inverted axes have their limits swapped.
+    pub fn limits(&self, axis: AxisType) -> (f64, f64) {
+        self.limits[&axis]
+    }
+
+    /// Converts a point in data coordinates on the primary axes to a pixel position.
+    pub fn data_to_point(&self, x: f64, y: f64) -> draw::Point {
+        self.data_to_point_on(AxisType::X, AxisType::Y, x, y)
+    }
+
+    /// Converts a point in data coordinates on the provided axes to a pixel position.
+    pub fn data_to_point_on(
+        &self,
+        xaxis: AxisType,
+        yaxis: AxisType,
+        x: f64,
+        y: f64,
+    ) -> draw::Point {
+        let xlim = self.limits(xaxis);
+        let ylim = self.limits(yaxis);
+
+        self.plot_area.fractional_to_point(draw::Point {
+            x: (x - xlim.0) / (xlim.1 - xlim.0),
+            y: (y - ylim.0) / (ylim.1 - ylim.0),
+        })
+    }
+
+    /// Draws a shape described by a [`backend::ShapeDescriptor`].
+    pub fn draw_shape(&mut self, desc: draw::ShapeDescriptor<'a>) {
+        self.commands.push(DrawCommand::Shape(desc));
+    }
+
+    /// Draws a line described by a [`backend::LineDescriptor`].
+    pub fn draw_line(&mut self, desc: draw::LineDescriptor<'a>) {
+        self.commands.push(DrawCommand::Line(desc));
+    }
+
+    /// Draws a curve described by a [`backend::CurveDescriptor`].
+    pub fn draw_curve(&mut self, desc: draw::CurveDescriptor<'a>) {
+        self.commands.push(DrawCommand::Curve(desc));
+    }
+
+    /// Fills a region described by a [`backend::FillDescriptor`].
+    pub fn fill_region(&mut self, desc: draw::FillDescriptor) {
+        self.commands.push(DrawCommand::Fill(desc));
+    }
+
+    /// Draws text described by a [`backend::TextDescriptor`].
+    pub fn draw_text(&mut self, desc: draw::TextDescriptor) {
+        self.commands.push(DrawCommand::Text(desc));
+    }
+}
+impl<'a> Overlay<'a> {
+    pub(crate) fn new(plot_area: draw::Area, limits: BTreeMap<AxisType, (f64, f64)>) -> Self {
+        Self { plot_area, limits, commands: vec![] }
+    }
+
+    /// Draws all recorded commands to the backend, in the order they were added.
+    pub(crate) fn draw<B: backend::Canvas>(self, canvas: &mut B) -> Result<(), PltError> {
+        for command in self.commands {
+            match command {
+                DrawCommand::Shape(desc) => canvas.draw_shape(desc)?,
+                DrawCommand::Line(desc) => canvas.draw_line(desc)?,
+                DrawCommand::Curve(desc) => canvas.draw_curve(desc)?,
+                DrawCommand::Fill(desc) => canvas.fill_region(desc)?,
+                DrawCommand::Text(desc) => canvas.draw_text(desc)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A callback drawing on an [`Overlay`], stored by a subplot.
+#[derive(Clone)]
+pub(crate) struct OverlayFn<'a>(pub(crate) Rc<dyn Fn(&mut Overlay<'a>) + 'a>);
+impl fmt::Debug for OverlayFn<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("OverlayFn")
+    }
+}
+
+#[derive(Debug)]
+enum DrawCommand<'a> {
+    Shape(draw::ShapeDescriptor<'a>),
+    Line(draw::LineDescriptor<'a>),
+    Curve(draw::CurveDescriptor<'a>),
+    Fill(draw::FillDescriptor),
+    Text(draw::TextDescriptor),
+}
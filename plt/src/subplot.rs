@@ -1,19 +1,25 @@
+use crate::figure::round_limits;
+use crate::overlay::{Overlay, OverlayFn};
 use crate::{Color, FontName, PltError};
 
-use std::{array, fmt::{self, Formatter}, f64, iter};
+use std::{array, fmt::{self, Formatter}, f64, iter, rc::Rc};
 
 /// The object that represents a whole subplot and is used to draw plotted data.
+///
+/// Cloning a subplot copies its series, so changes to one clone do not affect the other.
 #[derive(Clone, Debug)]
 pub struct Subplot<'a> {
     pub(crate) format: SubplotFormat,
+    pub(crate) format_set: bool,
     pub(crate) plot_order: Vec<PlotType>,
-    pub(crate) plot_infos: Vec<PlotInfo<'a>>,
+    pub(crate) plot_infos: Vec<Option<PlotInfo<'a>>>,
     pub(crate) fill_infos: Vec<FillInfo<'a>>,
     pub(crate) title: String,
     pub(crate) xaxis: AxisBuf,
     pub(crate) yaxis: AxisBuf,
     pub(crate) secondary_xaxis: AxisBuf,
     pub(crate) secondary_yaxis: AxisBuf,
+    pub(crate) overlays: Vec<OverlayFn<'a>>,
 }
 impl<'a> Subplot<'a> {
     /// Returns a builder with default settings for constructing a subplot.
@@ -37,13 +43,25 @@ impl<'a> Subplot<'a> {
         }
     }
 
+    /// Returns a [`Plotter`] for plotting on the secondary y-axis, sharing the x-axis.
+    /// Shortcut for calling `.plotter().use_secondary_yaxis()`.
+    pub fn twinx<'b>(&'b mut self) -> Plotter<'a, 'b> {
+        self.plotter().use_secondary_yaxis()
+    }
+
+    /// Returns a [`Plotter`] for plotting on the secondary x-axis, sharing the y-axis.
+    /// Shortcut for calling `.plotter().use_secondary_xaxis()`.
+    pub fn twiny<'b>(&'b mut self) -> Plotter<'a, 'b> {
+        self.plotter().use_secondary_xaxis()
+    }
+
     /// Plots X, Y data on this subplot with default plot formatting.
     /// Shortcut for calling `.plotter().plot()` on a [`Subplot`].
     pub fn plot<Xs, Ys, Fx, Fy>(
         &mut self,
         xs: Xs,
         ys: Ys,
-    ) -> Result<(), PltError>
+    ) -> Result<SeriesHandle, PltError>
     where
         Fx: IntoF64,
         Fy: IntoF64,
@@ -60,13 +78,29 @@ impl<'a> Subplot<'a> {
         plotter.plot(xs, ys)
     }
 
+    /// Plots complex data on this subplot with default plot formatting,
+    /// with the real part as x and the imaginary part as y.
+    /// Shortcut for calling `.plotter().plot_complex()` on a [`Subplot`].
+    #[cfg(feature = "num-complex")]
+    pub fn plot_complex(
+        &mut self,
+        data: &'a [num_complex::Complex<f64>],
+    ) -> Result<SeriesHandle, PltError> {
+        let plotter = Plotter {
+            subplot: self,
+            desc: PlotDescriptor::default(),
+        };
+
+        plotter.plot_complex(data)
+    }
+
     /// Plots step plot data on this subplot with default plot formatting.
     /// Shortcut for calling `.plotter().step()` on a [`Subplot`].
     pub fn step<Xs, Ys, Fx, Fy>(
         &mut self,
         steps: Xs,
         ys: Ys,
-    ) -> Result<(), PltError>
+    ) -> Result<SeriesHandle, PltError>
     where
         Fx: IntoF64,
         Fy: IntoF64,
@@ -113,16 +147,298 @@ impl<'a> Subplot<'a> {
         filler.fill_between(xs, y1s, y2s)
     }
 
+    /// Fills an area between two curves with separate x-values on the subplot with default formatting.
+    /// Shortcut for calling `.filler().fill_between_xy()` on a [`Subplot`].
+    pub fn fill_between_xy<X1s, Y1s, X2s, Y2s, Fx1, Fy1, Fx2, Fy2>(
+        &mut self,
+        x1s: X1s,
+        y1s: Y1s,
+        x2s: X2s,
+        y2s: Y2s,
+    ) -> Result<(), PltError>
+    where
+        Fx1: IntoF64,
+        Fy1: IntoF64,
+        Fx2: IntoF64,
+        Fy2: IntoF64,
+        X1s: IntoIterator<Item=Fx1>,
+        Y1s: IntoIterator<Item=Fy1>,
+        X2s: IntoIterator<Item=Fx2>,
+        Y2s: IntoIterator<Item=Fy2>,
+        <X1s as IntoIterator>::IntoIter: iter::ExactSizeIterator
+            + iter::DoubleEndedIterator + Clone + 'a,
+        <Y1s as IntoIterator>::IntoIter: iter::ExactSizeIterator
+            + iter::DoubleEndedIterator + Clone + 'a,
+        <X2s as IntoIterator>::IntoIter: iter::ExactSizeIterator
+            + iter::DoubleEndedIterator + Clone + 'a,
+        <Y2s as IntoIterator>::IntoIter: iter::ExactSizeIterator
+            + iter::DoubleEndedIterator + Clone + 'a,
+    {
+        let filler = Filler {
+            subplot: self,
+            desc: FillDescriptor::default(),
+        };
+
+        filler.fill_between_xy(x1s, y1s, x2s, y2s)
+    }
+
+    /// Plots X, Y data with a shaded band between lower and upper bounds in the same color.
+    /// The band is drawn beneath the line.
+    pub fn plot_with_band<Xs, Ys, Ls, Us, Fx, Fy, Fl, Fu>(
+        &mut self,
+        xs: Xs,
+        ys: Ys,
+        lower: Ls,
+        upper: Us,
+    ) -> Result<SeriesHandle, PltError>
+    where
+        Fx: IntoF64,
+        Fy: IntoF64,
+        Fl: IntoF64,
+        Fu: IntoF64,
+        Xs: IntoIterator<Item=Fx>,
+        Ys: IntoIterator<Item=Fy>,
+        Ls: IntoIterator<Item=Fl>,
+        Us: IntoIterator<Item=Fu>,
+        <Xs as IntoIterator>::IntoIter: iter::ExactSizeIterator
+            + iter::DoubleEndedIterator + Clone + 'a,
+        <Ys as IntoIterator>::IntoIter: iter::ExactSizeIterator + Clone + 'a,
+        <Ls as IntoIterator>::IntoIter: iter::ExactSizeIterator
+            + iter::DoubleEndedIterator + Clone + 'a,
+        <Us as IntoIterator>::IntoIter: iter::ExactSizeIterator
+            + iter::DoubleEndedIterator + Clone + 'a,
+    {
+        let xs = xs.into_iter();
+        let lower = lower.into_iter();
+        let upper = upper.into_iter();
+
+        // check the band first, so a bad band does not leave a lone line behind
+        check_plot_data(&xs.clone().map(|f| f.f64()), &lower.clone().map(|f| f.f64()))?;
+        check_plot_data(&xs.clone().map(|f| f.f64()), &upper.clone().map(|f| f.f64()))?;
+
+        let handle = self.plot(xs.clone(), ys)?;
+        self.filler().match_series(handle).fill_between(xs, lower, upper)?;
+
+        // draw the band before the line
+        let len = self.plot_order.len();
+        self.plot_order.swap(len - 2, len - 1);
+
+        Ok(handle)
+    }
+
     /// Returns the format of this plot.
     pub fn format(&self) -> &SubplotFormat {
         &self.format
     }
+
+    /// Replaces the format of this plot, keeping its data and axis configuration.
+    pub fn set_format(&mut self, format: SubplotFormat) -> Result<(), PltError> {
+        format.validate()?;
+        self.format = format;
+        self.format_set = true;
+
+        Ok(())
+    }
+
+    /// Sets axis labels after building.
+    pub fn set_label(&mut self, axes: Axes, label: &str) {
+        for placement in axes.axis_types() {
+            self.axis_mut(placement).label = label.to_string();
+        }
+    }
+
+    /// Sets axis limits after building, rescaling any axes set to [`Limits::Auto`] or [`Limits::AutoNice`].
+    pub fn set_limits(&mut self, axes: Axes, limits: Limits) -> Result<(), PltError> {
+        let placements = axes.axis_types();
+        if let Limits::Manual { min, max } = limits {
+            for &placement in &placements {
+                check_limits(placement, (min, max))?;
+            }
+        }
+
+        for placement in placements {
+            let axis = self.axis_mut(placement);
+            if let Limits::Manual { min, max } = limits {
+                axis.limits = Some((min, max));
+                axis.span = Some((min, max));
+            }
+            axis.limit_policy = limits;
+        }
+
+        self.rescale();
+
+        Ok(())
+    }
+
+    /// Sets axis grid settings after building.
+    pub fn set_grid(&mut self, axes: Axes, grid: Grid) {
+        for placement in axes.axis_types() {
+            self.axis_mut(placement).grid = grid;
+        }
+    }
+
+    /// Returns whether any plotted series or fill uses an axis.
+    pub fn axis_in_use(&self, axis: AxisType) -> bool {
+        self.plot_infos.iter()
+            .flatten()
+            .any(|info| info.xaxis == axis || info.yaxis == axis)
+            || self.fill_infos.iter()
+            .any(|info| info.xaxis == axis || info.yaxis == axis)
+    }
+
+    /// Returns the limits an axis will be drawn with, either set manually or autoscaled to the plotted data.
+    pub fn limits(&self, axis: AxisType) -> (f64, f64) {
+        self.span_and_limits(axis).1
+    }
+
+    /// Returns the range of the plotted data on an axis, without autoscale margins,
+    /// or [`None`] if no data uses the axis.
+    pub fn data_bounds(&self, axis: AxisType) -> Option<(f64, f64)> {
+        self.plot_infos.iter()
+            .flatten()
+            .flat_map(|info| [
+                (info.xaxis, info.data.xmin(), info.data.xmax()),
+                (info.yaxis, info.data.ymin(), info.data.ymax()),
+            ])
+            .chain(self.fill_infos.iter().flat_map(|info| [
+                (info.xaxis, info.data.xmin(), info.data.xmax()),
+                (info.yaxis, info.data.ymin(), info.data.ymax()),
+            ]))
+            .filter(|(placement, _, _)| *placement == axis)
+            .map(|(_, min, max)| (min, max))
+            .reduce(|(min1, max1), (min2, max2)| (f64::min(min1, min2), f64::max(max1, max2)))
+    }
+
+    /// Returns a subplot with the same title, format and axis configuration but no plotted data,
+    /// for reusing a style across many subplots.
+    pub fn template<'b>(&self) -> Subplot<'b> {
+        let mut template = Subplot {
+            format: self.format.clone(),
+            format_set: self.format_set,
+            plot_order: vec![],
+            plot_infos: vec![],
+            fill_infos: vec![],
+            title: self.title.clone(),
+            xaxis: self.xaxis.clone(),
+            yaxis: self.yaxis.clone(),
+            secondary_xaxis: self.secondary_xaxis.clone(),
+            secondary_yaxis: self.secondary_yaxis.clone(),
+            overlays: vec![],
+        };
+        template.rescale();
+
+        template
+    }
+
+    /// Adds a callback for custom drawing on this subplot, called each time the subplot is drawn.
+    /// Overlays are drawn after plotted data, in the order they were added.
+    pub fn on_draw<F: Fn(&mut Overlay<'a>) + 'a>(&mut self, callback: F) {
+        self.overlays.push(OverlayFn(Rc::new(callback)));
+    }
+
+    /// Removes all plotted data, keeping formatting and axis configuration.
+    /// Autoscaled limits are reset, while manual limits are kept.
+    pub fn clear(&mut self) {
+        self.plot_order.clear();
+        self.plot_infos.clear();
+        self.fill_infos.clear();
+
+        self.rescale();
+    }
+
+    /// Replaces the data of a series plotted with [`Plotter::plot`], keeping its formatting.
+    /// Step series are updated with [`Self::update_step`].
+    pub fn update_series<Xs, Ys, Fx, Fy>(
+        &mut self,
+        handle: SeriesHandle,
+        xs: Xs,
+        ys: Ys,
+    ) -> Result<(), PltError>
+    where
+        Fx: IntoF64,
+        Fy: IntoF64,
+        Xs: IntoIterator<Item=Fx>,
+        Ys: IntoIterator<Item=Fy>,
+        <Xs as IntoIterator>::IntoIter: iter::ExactSizeIterator + Clone + 'a,
+        <Ys as IntoIterator>::IntoIter: iter::ExactSizeIterator + Clone + 'a,
+    {
+        let xdata = xs.into_iter().map(|f| f.f64());
+        let ydata = ys.into_iter().map(|f| f.f64());
+
+        let info = self.plot_infos.get(handle.0)
+            .and_then(|info| info.as_ref())
+            .ok_or(PltError::InvalidSeries(handle.0))?;
+        if info.data.is_step() {
+            return Err(series_error(handle.0, &info.label, PltError::InvalidData(
+                "step series should be updated with `update_step`".to_owned()
+            )));
+        }
+        check_plot_data(&xdata, &ydata).map_err(|e| series_error(handle.0, &info.label, e))?;
+
+        if let Some(Some(info)) = self.plot_infos.get_mut(handle.0) {
+            info.data = Box::new(PlotData::new(xdata, ydata));
+        }
+
+        self.rescale();
+
+        Ok(())
+    }
+
+    /// Replaces the data of a series plotted with [`Plotter::step`], keeping its formatting.
+    pub fn update_step<Xs, Ys, Fx, Fy>(
+        &mut self,
+        handle: SeriesHandle,
+        steps: Xs,
+        ys: Ys,
+    ) -> Result<(), PltError>
+    where
+        Fx: IntoF64,
+        Fy: IntoF64,
+        Xs: IntoIterator<Item=Fx>,
+        Ys: IntoIterator<Item=Fy>,
+        <Xs as IntoIterator>::IntoIter: iter::ExactSizeIterator + Clone + 'a,
+        <Ys as IntoIterator>::IntoIter: iter::ExactSizeIterator + Clone + 'a,
+    {
+        let step_data = steps.into_iter().map(|f| f.f64());
+        let ydata = ys.into_iter().map(|f| f.f64());
+
+        let info = self.plot_infos.get(handle.0)
+            .and_then(|info| info.as_ref())
+            .ok_or(PltError::InvalidSeries(handle.0))?;
+        if !info.data.is_step() {
+            return Err(series_error(handle.0, &info.label, PltError::InvalidData(
+                "series is not a step series, and should be updated with `update_series`".to_owned()
+            )));
+        }
+        check_step_data(&step_data, &ydata).map_err(|e| series_error(handle.0, &info.label, e))?;
+
+        if let Some(Some(info)) = self.plot_infos.get_mut(handle.0) {
+            info.data = Box::new(StepData::new(step_data, ydata));
+        }
+
+        self.rescale();
+
+        Ok(())
+    }
+
+    /// Removes a plotted series. Handles to other series remain valid.
+    pub fn remove_series(&mut self, handle: SeriesHandle) -> Result<(), PltError> {
+        let info = self.plot_infos.get_mut(handle.0)
+            .filter(|info| info.is_some())
+            .ok_or(PltError::InvalidSeries(handle.0))?;
+        *info = None;
+
+        self.rescale();
+
+        Ok(())
+    }
 }
 impl<'a> Subplot<'a> {
     /// Internal constructor.
     pub(crate) fn new(desc: &SubplotDescriptor) -> Self {
         Self {
-            format: desc.format.clone(),
+            format: desc.format.clone().unwrap_or_default(),
+            format_set: desc.format.is_some(),
             plot_order: vec![],
             plot_infos: vec![],
             fill_infos: vec![],
@@ -131,6 +447,7 @@ impl<'a> Subplot<'a> {
             yaxis: desc.yaxis.to_buf(),
             secondary_xaxis: desc.secondary_xaxis.to_buf(),
             secondary_yaxis: desc.secondary_yaxis.to_buf(),
+            overlays: vec![],
         }
     }
 }
@@ -140,7 +457,7 @@ impl<'a> Subplot<'a> {
         &mut self,
         desc: PlotDescriptor,
         data: D,
-    ) {
+    ) -> SeriesHandle {
         let line = if desc.line {
             Some(desc.line_format)
         } else {
@@ -152,61 +469,11 @@ impl<'a> Subplot<'a> {
             None
         };
 
-        let xaxis = match desc.xaxis {
-            AxisType::X => &mut self.xaxis,
-            AxisType::Y => &mut self.yaxis,
-            AxisType::SecondaryX => &mut self.secondary_xaxis,
-            AxisType::SecondaryY => &mut self.secondary_yaxis,
-        };
-        match xaxis.limit_policy {
-            Limits::Auto => {
-                // span
-                xaxis.span = if let Some((xmin, xmax)) = xaxis.span {
-                    Some((f64::min(xmin, data.xmin()), f64::max(xmax, data.xmax())))
-                } else {
-                    Some((data.xmin(), data.xmax()))
-                };
-
-                // limits
-                let (xmin, xmax) = xaxis.span.unwrap();
-                let extent = xmax - xmin;
-                xaxis.limits = if extent > 0.0 {
-                    Some((xmin - 0.05 * extent, xmax + 0.05 * extent))
-                } else {
-                    Some((xmin - 1.0, xmax + 1.0))
-                };
-            },
-            Limits::Manual { min: _, max: _ } => {},
-        };
-
-        let yaxis = match desc.yaxis {
-            AxisType::X => &mut self.xaxis,
-            AxisType::Y => &mut self.yaxis,
-            AxisType::SecondaryX => &mut self.secondary_xaxis,
-            AxisType::SecondaryY => &mut self.secondary_yaxis,
-        };
-        match yaxis.limit_policy {
-            Limits::Auto => {
-                // span
-                yaxis.span = if let Some((ymin, ymax)) = yaxis.span {
-                    Some((f64::min(ymin, data.ymin()), f64::max(ymax, data.ymax())))
-                } else {
-                    Some((data.ymin(), data.ymax()))
-                };
-
-                // limits
-                let (ymin, ymax) = yaxis.span.unwrap();
-                let extent = ymax - ymin;
-                yaxis.limits = if extent > 0.0 {
-                    Some((ymin - 0.05 * extent, ymax + 0.05 * extent))
-                } else {
-                    Some((ymin - 1.0, ymax + 1.0))
-                };
-            },
-            Limits::Manual { min: _, max: _ } => {},
-        };
+        let jitter = desc.jitter / 2.0;
+        self.axis_mut(desc.xaxis).autoscale(data.xmin() - jitter, data.xmax() + jitter);
+        self.axis_mut(desc.yaxis).autoscale(data.ymin(), data.ymax());
 
-        self.plot_infos.push(PlotInfo {
+        self.plot_infos.push(Some(PlotInfo {
             label: desc.label.to_string(),
             data: Box::new(data),
             line,
@@ -214,8 +481,19 @@ impl<'a> Subplot<'a> {
             xaxis: desc.xaxis,
             yaxis: desc.yaxis,
             pixel_perfect: desc.pixel_perfect,
-        });
+            line_widths: desc.line_widths,
+            segmented: desc.segmented,
+            jitter: desc.jitter,
+            marker_indices: desc.marker_indices,
+            step_risers: desc.step_risers,
+            sort_x: desc.sort_x,
+            closed: desc.closed,
+            dedupe_markers: desc.dedupe_markers,
+            colormap: desc.colormap,
+        }));
         self.plot_order.push(PlotType::Series);
+
+        SeriesHandle(self.plot_infos.len() - 1)
     }
 
     /// Internal fill between setup function.
@@ -224,68 +502,97 @@ impl<'a> Subplot<'a> {
         desc: FillDescriptor,
         data: D,
     ) {
-        let xaxis = match desc.xaxis {
-            AxisType::X => &mut self.xaxis,
-            AxisType::Y => &mut self.yaxis,
-            AxisType::SecondaryX => &mut self.secondary_xaxis,
-            AxisType::SecondaryY => &mut self.secondary_yaxis,
+        if desc.autoscale {
+            self.axis_mut(desc.xaxis).autoscale(data.xmin(), data.xmax());
+            self.axis_mut(desc.yaxis).autoscale(data.ymin(), data.ymax());
+        }
+
+        self.fill_infos.push(FillInfo {
+            label: desc.label.to_string(),
+            data: Box::new(data),
+            color_override: desc.color_override,
+            matched_series: desc.matched_series,
+            xaxis: desc.xaxis,
+            yaxis: desc.yaxis,
+            autoscale: desc.autoscale,
+        });
+        self.plot_order.push(PlotType::Fill);
+    }
+
+    /// Returns the axis corresponding to an [`AxisType`].
+    pub(crate) fn axis(&self, axis: AxisType) -> &AxisBuf {
+        match axis {
+            AxisType::X => &self.xaxis,
+            AxisType::Y => &self.yaxis,
+            AxisType::SecondaryX => &self.secondary_xaxis,
+            AxisType::SecondaryY => &self.secondary_yaxis,
+        }
+    }
+
+    /// Returns the span and limits of an axis, with autoscaled limits rounded if set in the format.
+    /// If the axis has no data, the values of the opposite axis are used, otherwise (-1.0, 1.0).
+    pub(crate) fn span_and_limits(&self, placement: AxisType) -> ((f64, f64), (f64, f64)) {
+        let axis = self.axis(placement);
+        let opposite_axis = match placement {
+            AxisType::X => &self.secondary_xaxis,
+            AxisType::SecondaryX => &self.xaxis,
+            AxisType::Y => &self.secondary_yaxis,
+            AxisType::SecondaryY => &self.yaxis,
         };
-        match xaxis.limit_policy {
-            Limits::Auto => {
-                // span
-                xaxis.span = if let Some((xmin, xmax)) = xaxis.span {
-                    Some((f64::min(xmin, data.xmin()), f64::max(xmax, data.xmax())))
-                } else {
-                    Some((data.xmin(), data.xmax()))
-                };
 
-                // limits
-                let (xmin, xmax) = xaxis.span.unwrap();
-                let extent = xmax - xmin;
-                xaxis.limits = if extent > 0.0 {
-                    Some((xmin - 0.05 * extent, xmax + 0.05 * extent))
-                } else {
-                    Some((xmin - 1.0, xmax + 1.0))
-                };
-            },
-            Limits::Manual { min: _, max: _ } => {},
+        let (span, limits) = if let (Some(span), Some(limits)) = (axis.span, axis.limits) {
+            (span, limits)
+        } else if let (Some(span), Some(limits)) = (opposite_axis.span, opposite_axis.limits) {
+            (span, limits)
+        } else {
+            ((-1.0, 1.0), (-1.0, 1.0))
+        };
+
+        // rounded limits are chosen so ticks across them land on round numbers
+        let limits = match (axis.limit_policy, self.format.round_limits) {
+            (Limits::Auto, Some(sig_figs)) => round_limits(limits, sig_figs),
+            _ => limits,
         };
 
-        let yaxis = match desc.yaxis {
+        (span, limits)
+    }
+
+    /// Returns the axis corresponding to an [`AxisType`].
+    fn axis_mut(&mut self, axis: AxisType) -> &mut AxisBuf {
+        match axis {
             AxisType::X => &mut self.xaxis,
             AxisType::Y => &mut self.yaxis,
             AxisType::SecondaryX => &mut self.secondary_xaxis,
             AxisType::SecondaryY => &mut self.secondary_yaxis,
-        };
-        match yaxis.limit_policy {
-            Limits::Auto => {
-                // span
-                yaxis.span = if let Some((ymin, ymax)) = yaxis.span {
-                    Some((f64::min(ymin, data.ymin()), f64::max(ymax, data.ymax())))
-                } else {
-                    Some((data.ymin(), data.ymax()))
-                };
+        }
+    }
 
-                // limits
-                let (ymin, ymax) = yaxis.span.unwrap();
-                let extent = ymax - ymin;
-                yaxis.limits = if extent > 0.0 {
-                    Some((ymin - 0.05 * extent, ymax + 0.05 * extent))
-                } else {
-                    Some((ymin - 1.0, ymax + 1.0))
-                };
-            },
-            Limits::Manual { min: _, max: _ } => {},
-        };
+    /// Recomputes autoscaled limits from all plotted data.
+    fn rescale(&mut self) {
+        for placement in AxisType::iter() {
+            let axis = self.axis_mut(placement);
+            if let Limits::Auto | Limits::AutoNice = axis.limit_policy {
+                axis.span = None;
+                axis.limits = None;
+            }
+        }
 
-        self.fill_infos.push(FillInfo {
-            label: desc.label.to_string(),
-            data: Box::new(data),
-            color_override: desc.color_override,
-            xaxis: desc.xaxis,
-            yaxis: desc.yaxis,
-        });
-        self.plot_order.push(PlotType::Fill);
+        let extents = self.plot_infos.iter()
+            .flatten()
+            .map(|info| (
+                (info.xaxis, info.data.xmin() - info.jitter / 2.0, info.data.xmax() + info.jitter / 2.0),
+                (info.yaxis, info.data.ymin(), info.data.ymax()),
+            ))
+            .chain(self.fill_infos.iter().filter(|info| info.autoscale).map(|info| (
+                (info.xaxis, info.data.xmin(), info.data.xmax()),
+                (info.yaxis, info.data.ymin(), info.data.ymax()),
+            )))
+            .collect::<Vec<_>>();
+
+        for ((xaxis, xmin, xmax), (yaxis, ymin, ymax)) in extents {
+            self.axis_mut(xaxis).autoscale(xmin, xmax);
+            self.axis_mut(yaxis).autoscale(ymin, ymax);
+        }
     }
 }
 
@@ -295,10 +602,18 @@ pub struct SubplotBuilder<'a> {
 }
 impl<'a> SubplotBuilder<'a> {
     /// Builds the subplot.
+    /// The configuration is not checked, see [`Self::try_build`].
     pub fn build(self) -> Subplot<'a> {
         Subplot::new(&self.desc)
     }
 
+    /// Builds the subplot, returning an error if the configuration is invalid.
+    pub fn try_build(self) -> Result<Subplot<'a>, PltError> {
+        self.desc.validate()?;
+
+        Ok(Subplot::new(&self.desc))
+    }
+
     /// Sets the title of the subplot.
     pub fn title(mut self, title: &'a str) -> Self {
         self.desc.title = title;
@@ -307,7 +622,7 @@ impl<'a> SubplotBuilder<'a> {
 
     /// Sets the format of the subplot.
     pub fn format(mut self, format: SubplotFormat) -> Self {
-        self.desc.format = format;
+        self.desc.format = Some(format);
         self
     }
 
@@ -355,6 +670,16 @@ impl<'a> SubplotBuilder<'a> {
         self.limits(Axes::Y, limits)
     }
 
+    /// Sets the padding added to autoscaled axis limits.
+    pub fn limit_margin(mut self, axes: Axes, margin: AutoLimitMargin) -> Self {
+        let axes = self.axes(axes);
+        for axis in axes {
+            axis.margin = margin;
+        }
+
+        self
+    }
+
     /// Sets axis grid settings.
     pub fn grid(mut self, axes: Axes, grid: Grid) -> Self {
         let axes = self.axes(axes);
@@ -369,6 +694,16 @@ impl<'a> SubplotBuilder<'a> {
     pub fn standard_grid(self) -> Self {
         self.grid(Axes::BothPrimary, Grid::Major)
     }
+    /// Turns on horizontal grid lines only, from the major ticks of the primary y-axis.
+    /// Shortcut for calling `.grid(Axes::Y, Grid::Major)` and `.grid(Axes::BothX, Grid::None)`.
+    pub fn horizontal_grid(self) -> Self {
+        self.grid(Axes::Y, Grid::Major).grid(Axes::BothX, Grid::None)
+    }
+    /// Turns on vertical grid lines only, from the major ticks of the primary x-axis.
+    /// Shortcut for calling `.grid(Axes::X, Grid::Major)` and `.grid(Axes::BothY, Grid::None)`.
+    pub fn vertical_grid(self) -> Self {
+        self.grid(Axes::X, Grid::Major).grid(Axes::BothY, Grid::None)
+    }
 
     /// Sets major tick mark locations.
     pub fn major_tick_marks(mut self, axes: Axes, spacing: TickSpacing) -> Self {
@@ -419,6 +754,39 @@ impl<'a> SubplotBuilder<'a> {
 
         self
     }
+
+    /// Sets the color of axis lines and their ticks.
+    /// By default, the subplot format's `line_color` is used.
+    pub fn line_color(mut self, axes: Axes, color: Color) -> Self {
+        let axes = self.axes(axes);
+        for axis in axes {
+            axis.line_color = Some(color);
+        }
+
+        self
+    }
+
+    /// Sets the length of major ticks on axes, in dots (pixels). Minor ticks are half as long.
+    /// By default, the subplot format's `tick_length` is used.
+    pub fn tick_length(mut self, axes: Axes, length: u32) -> Self {
+        let axes = self.axes(axes);
+        for axis in axes {
+            axis.tick_length = Some(length);
+        }
+
+        self
+    }
+
+    /// Sets which edge of the plot y-values start from.
+    /// By default, [`Origin::Lower`] is used.
+    pub fn origin(mut self, origin: Origin) -> Self {
+        let axes = self.axes(Axes::BothY);
+        for axis in axes {
+            axis.inverted = matches!(origin, Origin::Upper);
+        }
+
+        self
+    }
 }
 impl<'a> SubplotBuilder<'a> {
     fn axes<'b>(&'b mut self, axes: Axes) -> Vec<&'b mut AxisDescriptor<&'a str>> {
@@ -467,6 +835,32 @@ pub enum Axes {
     All,
 }
 
+impl Axes {
+    /// The individual axes identified.
+    pub(crate) fn axis_types(self) -> Vec<AxisType> {
+        match self {
+            Self::X => vec![AxisType::X],
+            Self::Y => vec![AxisType::Y],
+            Self::SecondaryX => vec![AxisType::SecondaryX],
+            Self::SecondaryY => vec![AxisType::SecondaryY],
+            Self::BothX => vec![AxisType::X, AxisType::SecondaryX],
+            Self::BothY => vec![AxisType::Y, AxisType::SecondaryY],
+            Self::BothPrimary => vec![AxisType::X, AxisType::Y],
+            Self::BothSecondary => vec![AxisType::SecondaryX, AxisType::SecondaryY],
+            Self::All => AxisType::iter().collect(),
+        }
+    }
+}
+
+/// Identifies a single plot axis.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Hash, Copy, Clone, Debug)]
+pub enum AxisType {
+    X,
+    Y,
+    SecondaryX,
+    SecondaryY,
+}
+
 /// The formatting for a subplot.
 #[derive(Clone, Debug)]
 pub struct SubplotFormat {
@@ -482,6 +876,11 @@ pub struct SubplotFormat {
     pub line_color: Color,
     /// The color of grid lines.
     pub grid_color: Color,
+    /// Whether plotted data is antialiased.
+    pub antialias: bool,
+    /// Whether nonplot lines, such as axes, ticks and grid lines, are antialiased.
+    /// Turning this off draws crisp thin lines.
+    pub antialias_nonplot: bool,
     /// The name of the default font used.
     pub font_name: FontName,
     /// The size of the default font used.
@@ -495,8 +894,38 @@ pub struct SubplotFormat {
     /// Overrides the default length of minor tick marks.
     /// Otherwise computed from [`Self::tick_length`].
     pub override_minor_tick_length: Option<u32>,
+    /// Overrides the gap between tick marks and their labels, in dots (pixels).
+    /// Otherwise computed from [`Self::font_size`].
+    pub tick_label_pad: Option<u32>,
+    /// If set, limits of axes set to [`Limits::Auto`] are rounded outward to this many significant figures
+    /// of the axis extent, such as `(-0.1, 1.1)` instead of `(-0.05, 1.05)` for 2 figures.
+    /// Applies both when drawn and to [`Subplot::limits`]. Automatic major ticks fall on round steps within them.
+    /// Must be at least 1.
+    pub round_limits: Option<u8>,
+    /// The minimum distance between automatically placed major ticks, in dots (pixels).
+    /// Fewer ticks are placed on small subplots to keep this spacing. Ignored for [`TickSpacing::Count`].
+    pub min_tick_spacing: u32,
     /// The default colors cycled through for plot marker and line colors.
     pub color_cycle: Vec<Color>,
+    /// Line styles cycled through each time [`Self::color_cycle`] repeats,
+    /// for solid lines colored from the cycle. Unused if empty.
+    pub style_cycle: Vec<LineStyle>,
+    /// The alpha value of fills colored from [`Self::color_cycle`] or a matched series, from 0 to 1.
+    pub band_alpha: f64,
+    /// Whether to draw triangles at the plot edges where series data extends beyond the limits.
+    pub clip_indicators: bool,
+    /// Whether to hide the first and last major tick labels on each axis, to avoid crowding the corners.
+    pub trim_edge_labels: bool,
+    /// Seed for randomized rendering, so output is reproducible.
+    /// Currently only used by [`Plotter::jitter`].
+    pub seed: u64,
+    /// Where grid lines are drawn in the stacking order of the subplot.
+    ///
+    /// From bottom to top, a subplot draws its plot background, plotted data, then axes.
+    /// Axis lines, ticks and labels are always drawn last.
+    pub grid_layer: GridLayer,
+    /// If set, a border is drawn around the whole area given to the subplot in its figure.
+    pub cell_border: Option<Border>,
 }
 impl SubplotFormat {
     /// Constructor for a dark themed format.
@@ -523,9 +952,187 @@ impl SubplotFormat {
             tick_length: 8,
             tick_direction: TickDirection::Inner,
             override_minor_tick_length: None,
+            tick_label_pad: None,
+            round_limits: None,
+            color_cycle,
+            clip_indicators: false,
+            trim_edge_labels: false,
+            seed: 0,
+            grid_layer: GridLayer::BelowData,
+            cell_border: None,
+            min_tick_spacing: 0,
+            band_alpha: 0.5,
+            style_cycle: vec![],
+            antialias: true,
+            antialias_nonplot: true,
+        }
+    }
+
+    /// Constructor for a light format using the Solarized palette.
+    pub fn solarized_light() -> Self {
+        let line_color = Color { r: 0.345, g: 0.431, b: 0.459, a: 1.0 }; // base01
+
+        Self {
+            default_marker_color: line_color,
+            default_fill_color: Color { r: 0.863, g: 0.196, b: 0.184, a: 0.5 },
+            plot_color: Color { r: 0.992, g: 0.965, b: 0.890, a: 1.0 }, // base3
+            grid_color: Color { r: 0.933, g: 0.910, b: 0.835, a: 1.0 }, // base2
+            line_width: 2,
+            line_color,
+            font_name: FontName::default(),
+            font_size: 20.0,
+            text_color: line_color,
+            tick_length: 8,
+            tick_direction: TickDirection::Inner,
+            override_minor_tick_length: None,
+            tick_label_pad: None,
+            round_limits: None,
+            color_cycle: solarized_accents(),
+            clip_indicators: false,
+            trim_edge_labels: false,
+            seed: 0,
+            grid_layer: GridLayer::BelowData,
+            cell_border: None,
+            min_tick_spacing: 0,
+            band_alpha: 0.5,
+            style_cycle: vec![],
+            antialias: true,
+            antialias_nonplot: true,
+        }
+    }
+
+    /// Constructor for a dark format using the Solarized palette.
+    pub fn solarized_dark() -> Self {
+        let line_color = Color { r: 0.514, g: 0.580, b: 0.588, a: 1.0 }; // base0
+
+        Self {
+            default_marker_color: line_color,
+            default_fill_color: Color { r: 0.863, g: 0.196, b: 0.184, a: 0.5 },
+            plot_color: Color { r: 0.000, g: 0.169, b: 0.212, a: 1.0 }, // base03
+            grid_color: Color { r: 0.027, g: 0.212, b: 0.259, a: 1.0 }, // base02
+            line_width: 2,
+            line_color,
+            font_name: FontName::default(),
+            font_size: 20.0,
+            text_color: line_color,
+            tick_length: 8,
+            tick_direction: TickDirection::Inner,
+            override_minor_tick_length: None,
+            tick_label_pad: None,
+            round_limits: None,
+            color_cycle: solarized_accents(),
+            clip_indicators: false,
+            trim_edge_labels: false,
+            seed: 0,
+            grid_layer: GridLayer::BelowData,
+            cell_border: None,
+            min_tick_spacing: 0,
+            band_alpha: 0.5,
+            style_cycle: vec![],
+            antialias: true,
+            antialias_nonplot: true,
+        }
+    }
+
+    /// Constructor for a high contrast format with thick lines and colorblind safe colors.
+    pub fn high_contrast() -> Self {
+        let color_cycle = vec![
+            Color { r: 0.000, g: 0.447, b: 0.698, a: 1.0 }, // blue
+            Color { r: 0.835, g: 0.369, b: 0.000, a: 1.0 }, // vermillion
+            Color { r: 0.000, g: 0.620, b: 0.451, a: 1.0 }, // green
+            Color { r: 0.800, g: 0.475, b: 0.655, a: 1.0 }, // purple
+            Color { r: 0.902, g: 0.624, b: 0.000, a: 1.0 }, // orange
+            Color { r: 0.337, g: 0.706, b: 0.914, a: 1.0 }, // sky blue
+        ];
+
+        Self {
+            default_marker_color: Color::BLACK,
+            default_fill_color: Color { r: 0.835, g: 0.369, b: 0.000, a: 0.5 },
+            plot_color: Color::WHITE,
+            grid_color: Color { r: 0.500, g: 0.500, b: 0.500, a: 1.0 },
+            line_width: 3,
+            line_color: Color::BLACK,
+            font_name: FontName::default(),
+            font_size: 22.0,
+            text_color: Color::BLACK,
+            tick_length: 10,
+            tick_direction: TickDirection::Both,
+            override_minor_tick_length: None,
+            tick_label_pad: None,
+            round_limits: None,
             color_cycle,
+            clip_indicators: false,
+            trim_edge_labels: false,
+            seed: 0,
+            grid_layer: GridLayer::BelowData,
+            cell_border: None,
+            min_tick_spacing: 0,
+            band_alpha: 0.5,
+            style_cycle: vec![],
+            antialias: true,
+            antialias_nonplot: true,
         }
     }
+
+    /// Constructor for a minimal format with thin, light axis lines and outer ticks.
+    pub fn minimal() -> Self {
+        let text_color = Color { r: 0.250, g: 0.250, b: 0.250, a: 1.0 };
+
+        Self {
+            default_marker_color: text_color,
+            default_fill_color: Color { r: 1.0, g: 0.0, b: 0.0, a: 0.5 },
+            plot_color: Color::TRANSPARENT,
+            grid_color: Color { r: 0.900, g: 0.900, b: 0.900, a: 1.0 },
+            line_width: 1,
+            line_color: Color { r: 0.600, g: 0.600, b: 0.600, a: 1.0 },
+            font_name: FontName::default(),
+            font_size: 18.0,
+            text_color,
+            tick_length: 6,
+            tick_direction: TickDirection::Outer,
+            override_minor_tick_length: None,
+            tick_label_pad: None,
+            round_limits: None,
+            color_cycle: SubplotFormat::default().color_cycle,
+            clip_indicators: false,
+            trim_edge_labels: false,
+            seed: 0,
+            grid_layer: GridLayer::BelowData,
+            cell_border: None,
+            min_tick_spacing: 0,
+            band_alpha: 0.5,
+            style_cycle: vec![],
+            antialias: true,
+            antialias_nonplot: true,
+        }
+    }
+}
+impl SubplotFormat {
+    /// Checks that the format can be drawn.
+    pub(crate) fn validate(&self) -> Result<(), PltError> {
+        if !(self.font_size.is_finite() && self.font_size > 0.0) {
+            return Err(PltError::InvalidFormat(
+                format!("font size `{}` is not a positive number", self.font_size)
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.band_alpha) {
+            return Err(PltError::InvalidFormat(
+                format!("band alpha `{}` is not between 0 and 1", self.band_alpha)
+            ));
+        }
+        if self.round_limits == Some(0) {
+            return Err(PltError::InvalidFormat(
+                "limits cannot be rounded to zero significant figures".to_owned()
+            ));
+        }
+        if self.color_cycle.is_empty() && self.default_marker_color.a == 0.0 {
+            return Err(PltError::InvalidFormat(
+                "color cycle is empty and default marker color is transparent".to_owned()
+            ));
+        }
+
+        Ok(())
+    }
 }
 impl Default for SubplotFormat {
     fn default() -> Self {
@@ -550,11 +1157,36 @@ impl Default for SubplotFormat {
             tick_length: 8,
             tick_direction: TickDirection::Inner,
             override_minor_tick_length: None,
+            tick_label_pad: None,
+            round_limits: None,
             color_cycle,
+            clip_indicators: false,
+            trim_edge_labels: false,
+            seed: 0,
+            grid_layer: GridLayer::BelowData,
+            cell_border: None,
+            min_tick_spacing: 0,
+            band_alpha: 0.5,
+            style_cycle: vec![],
+            antialias: true,
+            antialias_nonplot: true,
         }
     }
 }
 
+/// The accent colors of the Solarized palette, used as a color cycle.
+fn solarized_accents() -> Vec<Color> {
+    vec![
+        Color { r: 0.149, g: 0.545, b: 0.824, a: 1.0 }, // blue
+        Color { r: 0.796, g: 0.294, b: 0.086, a: 1.0 }, // orange
+        Color { r: 0.522, g: 0.600, b: 0.000, a: 1.0 }, // green
+        Color { r: 0.827, g: 0.212, b: 0.510, a: 1.0 }, // magenta
+        Color { r: 0.165, g: 0.631, b: 0.596, a: 1.0 }, // cyan
+        Color { r: 0.710, g: 0.537, b: 0.000, a: 1.0 }, // yellow
+        Color { r: 0.424, g: 0.443, b: 0.769, a: 1.0 }, // violet
+    ]
+}
+
 /// Indicates which side of the axes ticks should point towards.
 #[derive(Copy, Clone, Debug)]
 pub enum TickDirection {
@@ -566,6 +1198,26 @@ pub enum TickDirection {
     Both,
 }
 
+/// Where grid lines are drawn relative to the plot background and data.
+#[derive(Copy, Clone, Debug)]
+pub enum GridLayer {
+    /// Grid lines are drawn beneath the plot background, showing through translucent backgrounds.
+    BelowBackground,
+    /// Grid lines are drawn over the plot background, beneath data.
+    BelowData,
+    /// Grid lines are drawn over data.
+    AboveData,
+}
+
+/// Which edge of a plot the y-axis starts from.
+#[derive(Copy, Clone, Debug)]
+pub enum Origin {
+    /// Y-values increase up the plot.
+    Lower,
+    /// Y-values increase down the plot, placing the first row of image-like data at the top.
+    Upper,
+}
+
 /// Describes how tick mark locations are determined, if at all.
 #[derive(Clone, Debug)]
 pub enum TickSpacing {
@@ -579,6 +1231,9 @@ pub enum TickSpacing {
     Count(u16),
     /// Tick marks are manually placed.
     Manual(Vec<f64>),
+    /// A set number of minor tick marks evenly spaced between each pair of major tick marks.
+    /// Only valid for minor ticks.
+    Between(u16),
 }
 
 /// Describes how and whether tick mark labels are set.
@@ -610,10 +1265,28 @@ pub enum Grid {
 pub enum Limits {
     /// Limits are determined by the library.
     Auto,
+    /// Limits are determined by the library, then rounded outward to round numbers,
+    /// so that major ticks fall on multiples of a round step. Autoscale margins are not added.
+    /// The step is chosen when drawn, for the number of major ticks that fit on the axis.
+    AutoNice,
     /// Limits are set manually.
     Manual { min: f64, max: f64 },
 }
 
+/// Padding added below and above the data when an axis is autoscaled, as fractions of the data span.
+#[derive(Copy, Clone, Debug)]
+pub struct AutoLimitMargin {
+    /// Padding below the minimum value.
+    pub low: f64,
+    /// Padding above the maximum value.
+    pub high: f64,
+}
+impl Default for AutoLimitMargin {
+    fn default() -> Self {
+        Self { low: 0.05, high: 0.05 }
+    }
+}
+
 /// Plots data on a subplot using the builder pattern.
 pub struct Plotter<'a, 'b> {
     subplot: &'b mut Subplot<'a>,
@@ -622,10 +1295,10 @@ pub struct Plotter<'a, 'b> {
 impl<'a, 'b> Plotter<'a, 'b> {
     /// Borrows data to be plotted and consumes the plotter.
     pub fn plot<Xs, Ys, Fx, Fy>(
-        self,
+        mut self,
         xs: Xs,
         ys: Ys,
-    ) -> Result<(), PltError>
+    ) -> Result<SeriesHandle, PltError>
     where
         Fx: IntoF64,
         Fy: IntoF64,
@@ -637,29 +1310,33 @@ impl<'a, 'b> Plotter<'a, 'b> {
         let xdata = xs.into_iter().map(|f| f.f64());
         let ydata = ys.into_iter().map(|f| f.f64());
 
-        if xdata.len() != ydata.len() {
-            return Err(PltError::InvalidData(
-                "Data is not correctly sized. x-data and y-data should be same length".to_owned()
-            ));
-        } else if xdata.clone().any(|x| x.is_nan()) {
-            return Err(PltError::InvalidData("x-data has NaN value".to_owned()));
-        } else if ydata.clone().any(|y| y.is_nan()) {
-            return Err(PltError::InvalidData("y-data has NaN value".to_owned()));
-        }
+        check_plot_data(&xdata, &ydata).map_err(|e| self.series_error(e))?;
+        self.desc.validate().map_err(|e| self.series_error(e))?;
+        // risers only apply to step plots
+        self.desc.step_risers = true;
 
         let data = PlotData::new(xdata, ydata);
 
-        self.subplot.plot_desc(self.desc, data);
+        Ok(self.subplot.plot_desc(self.desc, data))
+    }
 
-        Ok(())
+    /// Borrows complex data to be plotted and consumes the plotter.
+    /// Plots the real part as x and the imaginary part as y, drawing an Argand diagram.
+    #[cfg(feature = "num-complex")]
+    pub fn plot_complex(
+        self,
+        data: &'a [num_complex::Complex<f64>],
+    ) -> Result<SeriesHandle, PltError> {
+        self.plot(data.iter().map(|z| z.re), data.iter().map(|z| z.im))
     }
 
     /// Borrows step data to be plotted and consumes the plotter.
+    /// Step edges must be non-decreasing. Equal edges make a zero-width bin, drawn as a vertical riser only.
     pub fn step<Xs, Ys, Fx, Fy>(
         mut self,
         steps: Xs,
         ys: Ys,
-    ) -> Result<(), PltError>
+    ) -> Result<SeriesHandle, PltError>
     where
         Fx: IntoF64,
         Fy: IntoF64,
@@ -671,23 +1348,66 @@ impl<'a, 'b> Plotter<'a, 'b> {
         let step_data = steps.into_iter().map(|f| f.f64());
         let ydata = ys.into_iter().map(|f| f.f64());
 
-        if step_data.len() != ydata.len() + 1 {
-            return Err(PltError::InvalidData(
-                "Data is not correctly sized. There should be one more step than y-value".to_owned()
-            ));
-        } else if step_data.clone().any(|step| step.is_nan()) {
-            return Err(PltError::InvalidData("step-data has NaN value".to_owned()));
-        } else if ydata.clone().any(|y| y.is_nan()) {
-            return Err(PltError::InvalidData("y-data has NaN value".to_owned()));
-        }
-
+        check_step_data(&step_data, &ydata).map_err(|e| self.series_error(e))?;
+        self.desc.validate().map_err(|e| self.series_error(e))?;
         self.desc.pixel_perfect = true;
+        // steps are always drawn in order, and open
+        self.desc.sort_x = false;
+        self.desc.closed = false;
 
         let data = StepData::new(step_data, ydata);
 
-        self.subplot.plot_desc(self.desc, data);
+        Ok(self.subplot.plot_desc(self.desc, data))
+    }
 
-        Ok(())
+    /// Randomly offsets the x-value of each point by up to half the width in either direction when drawing.
+    /// Offsets are drawn from [`SubplotFormat::seed`]; plotted data is unchanged.
+    pub fn jitter(mut self, width: f64) -> Self {
+        self.desc.jitter = width;
+
+        self
+    }
+
+    /// Sets whether vertical risers are drawn between the treads of a step plot.
+    /// By default, risers are drawn.
+    pub fn step_risers(mut self, on: bool) -> Self {
+        self.desc.step_risers = on;
+
+        self
+    }
+
+    /// Sets whether the line is drawn through points in order of increasing x, instead of the order given.
+    /// Markers are unaffected. By default, points are connected in the order given.
+    pub fn sort_x(mut self, on: bool) -> Self {
+        self.desc.sort_x = on;
+
+        self
+    }
+
+    /// Sets whether the line connects the last point back to the first, drawing a closed shape.
+    /// By default, lines are open.
+    pub fn closed(mut self, on: bool) -> Self {
+        self.desc.closed = on;
+
+        self
+    }
+
+    /// Sets whether to skip markers that would be drawn close to an earlier marker,
+    /// within a grid of cells one marker size across.
+    /// Speeds up drawing dense scatter plots. By default, every marker is drawn.
+    pub fn dedupe_markers(mut self, on: bool) -> Self {
+        self.desc.dedupe_markers = on;
+
+        self
+    }
+
+    /// Colors the line and markers through a colormap, from the first point to the last,
+    /// showing the direction of a path. Overrides the line and marker colors.
+    /// By default, each series has a single color.
+    pub fn colorline(mut self, colormap: Colormap) -> Self {
+        self.desc.colormap = Some(colormap);
+
+        self
     }
 
     /// Uses the secondary X-Axis to reference x-data.
@@ -731,6 +1451,31 @@ impl<'a, 'b> Plotter<'a, 'b> {
         self
     }
 
+    /// Sets the width of each line segment, starting from the first point.
+    /// Segments without a width use the width set by [`Self::line_width`].
+    pub fn line_widths(mut self, widths: &[u32]) -> Self {
+        self.desc.line_widths = Some(widths.to_vec());
+
+        self
+    }
+
+    /// Sets whether lines are drawn as separate segments between points, instead of one connected curve.
+    /// By default, lines are drawn as one curve, unless segment widths are set.
+    pub fn segmented(mut self, on: bool) -> Self {
+        self.desc.segmented = on;
+
+        self
+    }
+
+    /// Shifts where the dash pattern of the line starts, in dots (pixels).
+    /// Incrementing the offset between frames animates the dashes.
+    /// Segmented lines and hidden step risers continue the pattern across segments.
+    pub fn line_dash_offset(mut self, offset: f64) -> Self {
+        self.desc.line_format.dash_offset = offset;
+
+        self
+    }
+
     /// Overrides the default line color.
     /// By default, line colors are determined by cycling through [`SubplotFormat::color_cycle`].
     pub fn line_color(mut self, color: Color) -> Self {
@@ -752,6 +1497,15 @@ impl<'a, 'b> Plotter<'a, 'b> {
         self
     }
 
+    /// Draws markers only at the points with the provided indices, in addition to any line.
+    /// Enables markers if they are not already drawn.
+    pub fn mark_indices(mut self, indices: &[usize]) -> Self {
+        self.desc.marker = true;
+        self.desc.marker_indices = Some(indices.to_vec());
+
+        self
+    }
+
     /// Sets the marker size.
     pub fn marker_size(mut self, size: u32) -> Self {
         self.desc.marker_format.size = size;
@@ -775,6 +1529,14 @@ impl<'a, 'b> Plotter<'a, 'b> {
         self
     }
 
+    /// Sets whether markers are drawn as outlines only, in the marker color.
+    /// By default, markers are filled.
+    pub fn marker_hollow(mut self, on: bool) -> Self {
+        self.desc.marker_format.hollow = on;
+
+        self
+    }
+
     /// Overrides the default outline color for marker outlines.
     /// By default, marker outline colors are determined by cycling through [`SubplotFormat::color_cycle`].
     pub fn marker_outline_color(mut self, color: Color) -> Self {
@@ -798,6 +1560,13 @@ impl<'a, 'b> Plotter<'a, 'b> {
         self
     }
 }
+impl Plotter<'_, '_> {
+    /// Adds the index and label of the series being plotted to a data error,
+    /// to tell which of many plotted series failed.
+    fn series_error(&self, err: PltError) -> PltError {
+        series_error(self.subplot.plot_infos.len(), &self.desc.label, err)
+    }
+}
 
 /// Fills a region of a subplot with a color.
 pub struct Filler<'a, 'b> {
@@ -827,6 +1596,10 @@ impl<'a, 'b> Filler<'a, 'b> {
         let y1data = y1s.into_iter().map(|f| f.f64());
         let y2data = y2s.into_iter().map(|f| f.f64());
 
+        // each curve shares the x-data, and is checked against it like a plotted series
+        check_plot_data(&xdata, &y1data)?;
+        check_plot_data(&xdata, &y2data)?;
+
         let data = FillBetweenData::new(xdata, y1data, y2data);
 
         self.subplot.fill_between_desc(self.desc, data);
@@ -834,6 +1607,44 @@ impl<'a, 'b> Filler<'a, 'b> {
         Ok(())
     }
 
+    /// Fills an area between two curves on the subplot, each with its own x-values.
+    /// The region is outlined along the first curve, then back along the second.
+    pub fn fill_between_xy<X1s, Y1s, X2s, Y2s, Fx1, Fy1, Fx2, Fy2>(
+        self,
+        x1s: X1s,
+        y1s: Y1s,
+        x2s: X2s,
+        y2s: Y2s,
+    ) -> Result<(), PltError>
+    where
+        Fx1: IntoF64,
+        Fy1: IntoF64,
+        Fx2: IntoF64,
+        Fy2: IntoF64,
+        X1s: IntoIterator<Item=Fx1>,
+        Y1s: IntoIterator<Item=Fy1>,
+        X2s: IntoIterator<Item=Fx2>,
+        Y2s: IntoIterator<Item=Fy2>,
+        <X1s as IntoIterator>::IntoIter: iter::ExactSizeIterator + iter::DoubleEndedIterator + Clone + 'a,
+        <Y1s as IntoIterator>::IntoIter: iter::ExactSizeIterator + iter::DoubleEndedIterator + Clone + 'a,
+        <X2s as IntoIterator>::IntoIter: iter::ExactSizeIterator + iter::DoubleEndedIterator + Clone + 'a,
+        <Y2s as IntoIterator>::IntoIter: iter::ExactSizeIterator + iter::DoubleEndedIterator + Clone + 'a,
+    {
+        let x1data = x1s.into_iter().map(|f| f.f64());
+        let y1data = y1s.into_iter().map(|f| f.f64());
+        let x2data = x2s.into_iter().map(|f| f.f64());
+        let y2data = y2s.into_iter().map(|f| f.f64());
+
+        check_plot_data(&x1data, &y1data)?;
+        check_plot_data(&x2data, &y2data)?;
+
+        let data = FillBetweenXyData::new(x1data, y1data, x2data, y2data);
+
+        self.subplot.fill_between_desc(self.desc, data);
+
+        Ok(())
+    }
+
     /// Uses the secondary Y-Axis to reference y-data.
     pub fn use_secondary_yaxis(mut self) -> Self {
         self.desc.yaxis = AxisType::SecondaryY;
@@ -850,12 +1661,100 @@ impl<'a, 'b> Filler<'a, 'b> {
 
     /// Overrides the default fill color.
     /// By default, line colors are determined by cycling through [`SubplotFormat::color_cycle`]
-    /// with an alpha value of 0.5.
+    /// with an alpha value of [`SubplotFormat::band_alpha`].
     pub fn color(mut self, color: Color) -> Self {
         self.desc.color_override = Some(color);
 
         self
     }
+
+    /// Fills with the color of a plotted series, with an alpha value of [`SubplotFormat::band_alpha`].
+    /// Falls back to the default fill color if the series has been removed.
+    pub fn match_series(mut self, handle: SeriesHandle) -> Self {
+        self.desc.matched_series = Some(handle);
+
+        self
+    }
+
+    /// Sets whether the filled region expands autoscaled axis limits.
+    /// By default, fills are included when autoscaling.
+    pub fn autoscale(mut self, on: bool) -> Self {
+        self.desc.autoscale = on;
+
+        self
+    }
+}
+
+/// Refers to a series plotted on a [`Subplot`], for later updating or removal.
+/// Handles are invalidated by [`Subplot::clear`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SeriesHandle(pub(crate) usize);
+
+/// Format of a border drawn around a subplot.
+#[derive(Copy, Clone, Debug)]
+pub struct Border {
+    /// The style of the border line.
+    pub style: LineStyle,
+    /// The width of the border line.
+    pub width: u32,
+    /// The color of the border line.
+    pub color: Color,
+}
+impl Default for Border {
+    fn default() -> Self {
+        Self {
+            style: LineStyle::Solid,
+            width: 2,
+            color: Color::BLACK,
+        }
+    }
+}
+
+/// Maps positions from 0 to 1 onto colors, blending linearly between evenly spaced colors.
+#[derive(Clone, Debug)]
+pub struct Colormap {
+    colors: Vec<Color>,
+}
+impl Colormap {
+    /// Creates a colormap passing through the provided colors in order.
+    pub fn new(colors: Vec<Color>) -> Result<Self, PltError> {
+        if colors.is_empty() {
+            return Err(PltError::InvalidData("colormap has no colors".to_owned()));
+        }
+
+        Ok(Self { colors })
+    }
+
+    /// A perceptually uniform colormap from dark purple, through blue and green, to yellow.
+    pub fn viridis() -> Self {
+        Self {
+            colors: vec![
+                Color { r: 0.267, g: 0.005, b: 0.329, a: 1.0 },
+                Color { r: 0.231, g: 0.322, b: 0.545, a: 1.0 },
+                Color { r: 0.129, g: 0.569, b: 0.549, a: 1.0 },
+                Color { r: 0.369, g: 0.788, b: 0.384, a: 1.0 },
+                Color { r: 0.992, g: 0.906, b: 0.145, a: 1.0 },
+            ],
+        }
+    }
+
+    /// Returns the color at a position from 0 to 1. Positions outside that range are clamped.
+    pub fn color_at(&self, position: f64) -> Color {
+        let position = if position.is_nan() { 0.0 } else { position.clamp(0.0, 1.0) };
+        let scaled = position * (self.colors.len() - 1) as f64;
+
+        let low = scaled.floor() as usize;
+        let high = usize::min(low + 1, self.colors.len() - 1);
+        let t = scaled - low as f64;
+
+        let (c1, c2) = (self.colors[low], self.colors[high]);
+        Color {
+            r: c1.r + (c2.r - c1.r) * t,
+            g: c1.g + (c2.g - c1.g) * t,
+            b: c1.b + (c2.b - c1.b) * t,
+            a: c1.a + (c2.a - c1.a) * t,
+        }
+    }
 }
 
 /// Plotting line styles.
@@ -885,8 +1784,8 @@ pub enum MarkerStyle {
 /// Describes the configuration of a [`Subplot`].
 #[derive(Clone, Debug)]
 pub(crate) struct SubplotDescriptor<'a> {
-    /// The format of this subplot.
-    pub format: SubplotFormat,
+    /// The format of this subplot, if set rather than left to the figure default.
+    pub format: Option<SubplotFormat>,
     /// The title displayed at the top of this subplot.
     pub title: &'a str,
     /// The default axis corresponding to x-values.
@@ -901,7 +1800,7 @@ pub(crate) struct SubplotDescriptor<'a> {
 impl Default for SubplotDescriptor<'_> {
     fn default() -> Self {
         Self {
-            format: SubplotFormat::default(),
+            format: None,
             title: "",
             xaxis: AxisDescriptor {
                 label: "",
@@ -914,6 +1813,10 @@ impl Default for SubplotDescriptor<'_> {
                 limits: None,
                 span: None,
                 visible: true,
+                margin: AutoLimitMargin::default(),
+                inverted: false,
+                line_color: None,
+                tick_length: None,
             },
             yaxis: AxisDescriptor {
                 label: "",
@@ -926,6 +1829,10 @@ impl Default for SubplotDescriptor<'_> {
                 limits: None,
                 span: None,
                 visible: true,
+                margin: AutoLimitMargin::default(),
+                inverted: false,
+                line_color: None,
+                tick_length: None,
             },
             secondary_xaxis: AxisDescriptor {
                 label: "",
@@ -938,6 +1845,10 @@ impl Default for SubplotDescriptor<'_> {
                 limits: None,
                 span: None,
                 visible: true,
+                margin: AutoLimitMargin::default(),
+                inverted: false,
+                line_color: None,
+                tick_length: None,
             },
             secondary_yaxis: AxisDescriptor {
                 label: "",
@@ -950,11 +1861,199 @@ impl Default for SubplotDescriptor<'_> {
                 limits: None,
                 span: None,
                 visible: true,
+                margin: AutoLimitMargin::default(),
+                inverted: false,
+                line_color: None,
+                tick_length: None,
             },
         }
     }
 }
 
+impl SubplotDescriptor<'_> {
+    /// Checks that the configuration can be drawn.
+    fn validate(&self) -> Result<(), PltError> {
+        if let Some(format) = &self.format {
+            format.validate()?;
+        }
+
+        for placement in AxisType::iter() {
+            let axis = match placement {
+                AxisType::X => &self.xaxis,
+                AxisType::Y => &self.yaxis,
+                AxisType::SecondaryX => &self.secondary_xaxis,
+                AxisType::SecondaryY => &self.secondary_yaxis,
+            };
+
+            if let Limits::Manual { min, max } = axis.limit_policy {
+                check_limits(placement, (min, max))?;
+            }
+            check_major_tick_marks(placement, &axis.major_tick_marks)?;
+
+            let margin = axis.margin;
+            if !(margin.low.is_finite() && margin.low >= 0.0 && margin.high.is_finite() && margin.high >= 0.0) {
+                return Err(PltError::InvalidData(
+                    format!("{} margin must be finite and non-negative", placement.name())
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Adds the index and label of a series to a data error.
+fn series_error(index: usize, label: &str, err: PltError) -> PltError {
+    match err {
+        PltError::InvalidData(message) => {
+            PltError::InvalidData(if label.is_empty() {
+                format!("series {}: {}", index, message)
+            } else {
+                format!("series {} `{}`: {}", index, label, message)
+            })
+        },
+        err => err,
+    }
+}
+
+/// Checks that X, Y data are the same length and all finite.
+fn check_plot_data<Ix, Iy>(xdata: &Ix, ydata: &Iy) -> Result<(), PltError>
+where
+    Ix: iter::ExactSizeIterator<Item=f64> + Clone,
+    Iy: iter::ExactSizeIterator<Item=f64> + Clone,
+{
+    if xdata.len() != ydata.len() {
+        return Err(PltError::InvalidData(
+            "Data is not correctly sized. x-data and y-data should be same length".to_owned()
+        ));
+    } else if xdata.len() == 0 {
+        // empty data would autoscale to infinite limits
+        return Err(PltError::InvalidData("series is empty".to_owned()));
+    } else if xdata.clone().any(|x| x.is_nan()) {
+        return Err(PltError::InvalidData("x-data has NaN value".to_owned()));
+    } else if ydata.clone().any(|y| y.is_nan()) {
+        return Err(PltError::InvalidData("y-data has NaN value".to_owned()));
+    } else if xdata.clone().any(|x| x.is_infinite()) {
+        return Err(PltError::InvalidData("x-data has infinite value".to_owned()));
+    } else if ydata.clone().any(|y| y.is_infinite()) {
+        return Err(PltError::InvalidData("y-data has infinite value".to_owned()));
+    }
+
+    Ok(())
+}
+
+/// Checks that step edges are one longer than the Y data, ascending and all finite.
+fn check_step_data<Is, Iy>(step_data: &Is, ydata: &Iy) -> Result<(), PltError>
+where
+    Is: iter::ExactSizeIterator<Item=f64> + Clone,
+    Iy: iter::ExactSizeIterator<Item=f64> + Clone,
+{
+    if step_data.len() != ydata.len() + 1 {
+        return Err(PltError::InvalidData(
+            "Data is not correctly sized. There should be one more step than y-value".to_owned()
+        ));
+    } else if ydata.len() == 0 {
+        return Err(PltError::InvalidData("series is empty".to_owned()));
+    } else if step_data.clone().any(|step| step.is_nan()) {
+        return Err(PltError::InvalidData("step-data has NaN value".to_owned()));
+    } else if ydata.clone().any(|y| y.is_nan()) {
+        return Err(PltError::InvalidData("y-data has NaN value".to_owned()));
+    } else if step_data.clone().any(|step| step.is_infinite()) {
+        return Err(PltError::InvalidData("step-data has infinite value".to_owned()));
+    } else if ydata.clone().any(|y| y.is_infinite()) {
+        return Err(PltError::InvalidData("y-data has infinite value".to_owned()));
+    } else if iter::zip(step_data.clone(), step_data.clone().skip(1)).any(|(a, b)| b < a) {
+        return Err(PltError::InvalidData("step-data edges are not in ascending order".to_owned()));
+    }
+
+    Ok(())
+}
+
+/// Rounds limits outward to a multiple of a round step, so that `nintervals` steps cover them.
+pub(crate) fn nice_limits(min: f64, max: f64, nintervals: u16) -> (f64, f64) {
+    let (min, max) = if max > min { (min, max) } else { (min - 1.0, max + 1.0) };
+    let nintervals = nintervals.max(1) as f64;
+
+    let mut step = nice_step((max - min) / nintervals);
+    loop {
+        let low = (min / step).floor() * step;
+        if low + nintervals * step >= max {
+            return (low, low + nintervals * step);
+        }
+        // the data does not fit from this starting point, try the next larger step
+        step = nice_step(step * (1.0 + 1e-9));
+    }
+}
+
+/// Places about `nticks` ticks on multiples of a round step within limits,
+/// or returns [`None`] if fewer than two ticks fit.
+pub(crate) fn nice_ticks((min, max): (f64, f64), nticks: u16) -> Option<Vec<f64>> {
+    if nticks < 2 || max <= min {
+        return None;
+    }
+
+    let step = nice_step((max - min) / (nticks - 1) as f64);
+    // allow for rounding error, so limits on a step keep their ticks
+    let first = (min / step - 1e-9).ceil() as i64;
+    let last = (max / step + 1e-9).floor() as i64;
+    if last <= first {
+        return None;
+    }
+
+    Some((first..=last).map(|n| n as f64 * step).collect())
+}
+
+/// The smallest 1, 2, 2.5 or 5 times a power of ten that is at least `value`.
+fn nice_step(value: f64) -> f64 {
+    let magnitude = 10f64.powf(value.log10().floor());
+
+    [1.0, 2.0, 2.5, 5.0].into_iter()
+        .map(|mantissa| mantissa * magnitude)
+        .find(|step| *step >= value)
+        .unwrap_or(10.0 * magnitude)
+}
+
+/// Checks that major tick marks do not use a spacing only meant for minor tick marks.
+pub(crate) fn check_major_tick_marks(placement: AxisType, spacing: &TickSpacing) -> Result<(), PltError> {
+    if let TickSpacing::Between(_) = spacing {
+        Err(PltError::InvalidData(format!(
+            "{} major tick marks cannot use `TickSpacing::Between`, which is only for minor tick marks",
+            placement.name()
+        )))
+    } else {
+        Ok(())
+    }
+}
+
+/// Checks that axis limits are ordered and not NaN, so that data maps onto the axis correctly.
+pub(crate) fn check_limits(placement: AxisType, (min, max): (f64, f64)) -> Result<(), PltError> {
+    if min.is_nan() || max.is_nan() {
+        Err(PltError::InvalidData(
+            format!("{} limits have NaN value", placement.name())
+        ))
+    } else if min.is_infinite() || max.is_infinite() {
+        Err(PltError::InvalidData(
+            format!("{} limits have infinite value", placement.name())
+        ))
+    } else if min == max {
+        // a zero extent would make every plotted value NaN
+        Err(PltError::InvalidData(format!(
+            "{} limits have zero extent, with min and max both `{}`",
+            placement.name(),
+            min,
+        )))
+    } else if min > max {
+        Err(PltError::InvalidData(format!(
+            "{} limits have min `{}` greater than max `{}`",
+            placement.name(),
+            min,
+            max,
+        )))
+    } else {
+        Ok(())
+    }
+}
+
 /// Represents different plottable dataset types.
 #[derive(Copy, Clone, Debug)]
 pub(crate) enum PlotType {
@@ -981,6 +2080,36 @@ pub(crate) struct PlotDescriptor {
     pub yaxis: AxisType,
     /// If plot points should be rounded to the nearest dot (pixel).
     pub pixel_perfect: bool,
+    /// Optionally sets the width of each line segment.
+    pub line_widths: Option<Vec<u32>>,
+    /// If lines should be drawn as separate segments.
+    pub segmented: bool,
+    /// The width of random x-offsets applied to points when drawing.
+    pub jitter: f64,
+    /// Optionally limits markers to the points with these indices.
+    pub marker_indices: Option<Vec<usize>>,
+    /// If vertical risers are drawn between the treads of step plots.
+    pub step_risers: bool,
+    /// If lines are drawn through points sorted by x.
+    pub sort_x: bool,
+    /// If lines connect the last point back to the first.
+    pub closed: bool,
+    /// If markers landing in a grid cell that already holds a marker are skipped.
+    pub dedupe_markers: bool,
+    /// Optionally colors points and segments by their position along the series.
+    pub colormap: Option<Colormap>,
+}
+impl PlotDescriptor {
+    /// Checks that the plot format can be drawn.
+    fn validate(&self) -> Result<(), PltError> {
+        if !(self.jitter.is_finite() && self.jitter >= 0.0) {
+            return Err(PltError::InvalidData(
+                format!("jitter width `{}` is not finite and non-negative", self.jitter)
+            ));
+        }
+
+        Ok(())
+    }
 }
 impl Default for PlotDescriptor {
     fn default() -> Self {
@@ -993,6 +2122,15 @@ impl Default for PlotDescriptor {
             xaxis: AxisType::X,
             yaxis: AxisType::Y,
             pixel_perfect: false,
+            line_widths: None,
+            segmented: false,
+            jitter: 0.0,
+            marker_indices: None,
+            step_risers: true,
+            sort_x: false,
+            closed: false,
+            dedupe_markers: false,
+            colormap: None,
         }
     }
 }
@@ -1004,18 +2142,24 @@ pub(crate) struct FillDescriptor {
     pub label: String,
     /// The color to fill the area with.
     pub color_override: Option<Color>,
+    /// A series whose color is used for the fill, if there is no color override.
+    pub matched_series: Option<SeriesHandle>,
     /// Which axis to use as the x-axis.
     pub xaxis: AxisType,
     /// Which axis to use as the y-axis.
     pub yaxis: AxisType,
+    /// If the filled region is included when autoscaling axis limits.
+    pub autoscale: bool,
 }
 impl Default for FillDescriptor {
     fn default() -> Self {
         Self {
             label: String::new(),
             color_override: None,
+            matched_series: None,
             xaxis: AxisType::X,
             yaxis: AxisType::Y,
+            autoscale: true,
         }
     }
 }
@@ -1029,6 +2173,8 @@ pub(crate) struct Line {
     pub width: u32,
     /// Optionally overrides the default color of the line.
     pub color_override: Option<Color>,
+    /// How far into the dash pattern the line starts, in dots (pixels).
+    pub dash_offset: f64,
 }
 impl Default for Line {
     fn default() -> Self {
@@ -1036,6 +2182,7 @@ impl Default for Line {
             style: LineStyle::Solid,
             width: 3,
             color_override: None,
+            dash_offset: 0.0,
         }
     }
 }
@@ -1053,6 +2200,8 @@ pub(crate) struct Marker {
     pub outline: bool,
     /// Format of an optional outline.
     pub outline_format: Line,
+    /// Whether to leave the marker unfilled, drawing only the outline.
+    pub hollow: bool,
 }
 impl Default for Marker {
     fn default() -> Self {
@@ -1065,6 +2214,7 @@ impl Default for Marker {
                 width: 2,
                 ..Default::default()
             },
+            hollow: false,
         }
     }
 }
@@ -1092,23 +2242,64 @@ pub(crate) struct AxisDescriptor<S: AsRef<str>> {
     pub span: Option<(f64, f64)>,
     /// Whether to draw the axis line.
     pub visible: bool,
+    /// Padding added beyond the span of an autoscaled axis.
+    pub margin: AutoLimitMargin,
+    /// Whether values increase towards the left or bottom of the plot.
+    pub inverted: bool,
+    /// The color of the axis line and ticks, overriding the subplot line color.
+    pub line_color: Option<Color>,
+    /// The length of major ticks on this axis, overriding the subplot tick length.
+    pub tick_length: Option<u32>,
 }
 
-#[derive(Eq, PartialEq, Hash, Copy, Clone, Debug)]
-pub(crate) enum AxisType {
-    X,
-    Y,
-    SecondaryX,
-    SecondaryY,
-}
+
 impl AxisType {
     pub(crate) fn iter() -> array::IntoIter<Self, 4> {
         [Self::X, Self::Y, Self::SecondaryX, Self::SecondaryY].into_iter()
     }
+    /// Human readable name of the axis, for error messages.
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Self::X => "x-axis",
+            Self::Y => "y-axis",
+            Self::SecondaryX => "secondary x-axis",
+            Self::SecondaryY => "secondary y-axis",
+        }
+    }
 }
 
 pub(crate) type AxisBuf = AxisDescriptor<String>;
 impl<S: AsRef<str>> AxisDescriptor<S> {
+    /// Expands the span of an autoscaled axis to include a range of values, and updates its limits.
+    fn autoscale(&mut self, min: f64, max: f64) {
+        match self.limit_policy {
+            Limits::Auto | Limits::AutoNice => {
+                // span
+                self.span = if let Some((span_min, span_max)) = self.span {
+                    Some((f64::min(span_min, min), f64::max(span_max, max)))
+                } else {
+                    Some((min, max))
+                };
+
+                // limits
+                let (min, max) = self.span.unwrap();
+                let extent = max - min;
+                self.limits = if let Limits::AutoNice = self.limit_policy {
+                    let nintervals = match self.major_tick_marks {
+                        TickSpacing::Count(n) if n >= 2 => n - 1,
+                        _ => 4,
+                    };
+                    Some(nice_limits(min, max, nintervals))
+                } else if extent > 0.0 {
+                    Some((min - self.margin.low * extent, max + self.margin.high * extent))
+                } else {
+                    Some((min - 1.0, max + 1.0))
+                };
+            },
+            Limits::Manual { min: _, max: _ } => {},
+        };
+    }
+
     fn to_buf(&self) -> AxisBuf {
         AxisBuf {
             label: self.label.as_ref().to_string(),
@@ -1121,6 +2312,10 @@ impl<S: AsRef<str>> AxisDescriptor<S> {
             limits: self.limits,
             span: self.span,
             visible: self.visible,
+            margin: self.margin,
+            inverted: self.inverted,
+            line_color: self.line_color,
+            tick_length: self.tick_length,
         }
     }
 }
@@ -1136,6 +2331,15 @@ pub(crate) struct PlotInfo<'a> {
     pub xaxis: AxisType,
     pub yaxis: AxisType,
     pub pixel_perfect: bool,
+    pub line_widths: Option<Vec<u32>>,
+    pub segmented: bool,
+    pub jitter: f64,
+    pub marker_indices: Option<Vec<usize>>,
+    pub step_risers: bool,
+    pub sort_x: bool,
+    pub closed: bool,
+    pub dedupe_markers: bool,
+    pub colormap: Option<Colormap>,
 }
 
 #[derive(Clone, Debug)]
@@ -1144,8 +2348,10 @@ pub(crate) struct FillInfo<'a> {
     pub label: String,
     pub data: Box<dyn FillData + 'a>,
     pub color_override: Option<Color>,
+    pub matched_series: Option<SeriesHandle>,
     pub xaxis: AxisType,
     pub yaxis: AxisType,
+    pub autoscale: bool,
 }
 
 pub trait IntoF64 {
@@ -1352,6 +2558,9 @@ where
     fn ymax(&self) -> f64 {
         self.ydata.clone().fold(f64::NEG_INFINITY, |a, b| a.max(b))
     }
+    fn is_step(&self) -> bool {
+        true
+    }
 }
 impl<Iedge, Idata> StepData<Iedge, Idata>
 where
@@ -1449,6 +2658,100 @@ where
     }
 }
 
+/// Holds borrowed data describing an area between two curves with separate x-values.
+#[derive(Copy, Clone)]
+pub(crate) struct FillBetweenXyData<Ix1, Iy1, Ix2, Iy2>
+where
+    Ix1: Iterator<Item=f64> + iter::ExactSizeIterator + iter::DoubleEndedIterator + Clone,
+    Iy1: Iterator<Item=f64> + iter::ExactSizeIterator + iter::DoubleEndedIterator + Clone,
+    Ix2: Iterator<Item=f64> + iter::ExactSizeIterator + iter::DoubleEndedIterator + Clone,
+    Iy2: Iterator<Item=f64> + iter::ExactSizeIterator + iter::DoubleEndedIterator + Clone,
+{
+    x1_data: Ix1,
+    y1_data: Iy1,
+    x2_data: Ix2,
+    y2_data: Iy2,
+}
+impl<Ix1, Iy1, Ix2, Iy2> fmt::Debug for FillBetweenXyData<Ix1, Iy1, Ix2, Iy2>
+where
+    Ix1: Iterator<Item=f64> + iter::ExactSizeIterator + iter::DoubleEndedIterator + Clone,
+    Iy1: Iterator<Item=f64> + iter::ExactSizeIterator + iter::DoubleEndedIterator + Clone,
+    Ix2: Iterator<Item=f64> + iter::ExactSizeIterator + iter::DoubleEndedIterator + Clone,
+    Iy2: Iterator<Item=f64> + iter::ExactSizeIterator + iter::DoubleEndedIterator + Clone,
+{
+    fn fmt(&self, _: &mut Formatter) -> Result<(), fmt::Error> {
+        Ok(())
+    }
+}
+impl<Ix1, Iy1, Ix2, Iy2> FillData for FillBetweenXyData<Ix1, Iy1, Ix2, Iy2>
+where
+    Ix1: Iterator<Item=f64> + iter::ExactSizeIterator + iter::DoubleEndedIterator + Clone,
+    Iy1: Iterator<Item=f64> + iter::ExactSizeIterator + iter::DoubleEndedIterator + Clone,
+    Ix2: Iterator<Item=f64> + iter::ExactSizeIterator + iter::DoubleEndedIterator + Clone,
+    Iy2: Iterator<Item=f64> + iter::ExactSizeIterator + iter::DoubleEndedIterator + Clone,
+{
+    fn curve1<'b>(&'b self) -> Box<dyn DoubleEndedIterator<Item = (f64, f64)> + 'b> {
+        Box::new(iter::zip(
+            self.x1_data.clone(),
+            self.y1_data.clone(),
+        ))
+    }
+
+    fn curve2<'b>(&'b self) -> Box<dyn DoubleEndedIterator<Item = (f64, f64)> + 'b> {
+        Box::new(iter::zip(
+            self.x2_data.clone(),
+            self.y2_data.clone(),
+        ))
+    }
+
+    fn xmin(&self) -> f64 {
+        f64::min(
+            self.x1_data.clone().fold(f64::INFINITY, |a, b| a.min(b)),
+            self.x2_data.clone().fold(f64::INFINITY, |a, b| a.min(b)),
+        )
+    }
+    fn xmax(&self) -> f64 {
+        f64::max(
+            self.x1_data.clone().fold(f64::NEG_INFINITY, |a, b| a.max(b)),
+            self.x2_data.clone().fold(f64::NEG_INFINITY, |a, b| a.max(b)),
+        )
+    }
+    fn ymin(&self) -> f64 {
+        f64::min(
+            self.y1_data.clone().fold(f64::INFINITY, |a, b| a.min(b)),
+            self.y2_data.clone().fold(f64::INFINITY, |a, b| a.min(b)),
+        )
+    }
+    fn ymax(&self) -> f64 {
+        f64::max(
+            self.y1_data.clone().fold(f64::NEG_INFINITY, |a, b| a.max(b)),
+            self.y2_data.clone().fold(f64::NEG_INFINITY, |a, b| a.max(b)),
+        )
+    }
+}
+impl<Ix1, Iy1, Ix2, Iy2> FillBetweenXyData<Ix1, Iy1, Ix2, Iy2>
+where
+    Ix1: Iterator<Item=f64> + iter::ExactSizeIterator + iter::DoubleEndedIterator + Clone,
+    Iy1: Iterator<Item=f64> + iter::ExactSizeIterator + iter::DoubleEndedIterator + Clone,
+    Ix2: Iterator<Item=f64> + iter::ExactSizeIterator + iter::DoubleEndedIterator + Clone,
+    Iy2: Iterator<Item=f64> + iter::ExactSizeIterator + iter::DoubleEndedIterator + Clone,
+{
+    /// Main constructor, taking separate x-values and y-values for each curve.
+    pub fn new(
+        x1s: Ix1,
+        y1s: Iy1,
+        x2s: Ix2,
+        y2s: Iy2,
+    ) -> Self {
+        Self {
+            x1_data: x1s,
+            y1_data: y1s,
+            x2_data: x2s,
+            y2_data: y2s,
+        }
+    }
+}
+
 // traits
 
 /// Implemented for data that can be represented by pairs of floats to be plotted.
@@ -1463,6 +2766,10 @@ pub(crate) trait SeriesData: dyn_clone::DynClone + fmt::Debug {
     fn ymin(&self) -> f64;
     /// The largest y-value.
     fn ymax(&self) -> f64;
+    /// Whether the data describes the treads of a step plot, as pairs of points.
+    fn is_step(&self) -> bool {
+        false
+    }
 }
 
 dyn_clone::clone_trait_object!(SeriesData);
@@ -1483,3 +2790,130 @@ pub(crate) trait FillData: dyn_clone::DynClone + fmt::Debug {
 }
 
 dyn_clone::clone_trait_object!(FillData);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_invalid_data<T>(result: Result<T, PltError>) -> bool {
+        matches!(result, Err(PltError::InvalidData(_)))
+    }
+
+    #[test]
+    fn plot_rejects_infinite_values() {
+        let mut subplot = Subplot::builder().build();
+
+        for bad in [f64::INFINITY, f64::NEG_INFINITY] {
+            assert!(is_invalid_data(subplot.plot(vec![0.0, bad], vec![0.0, 1.0])));
+            assert!(is_invalid_data(subplot.plot(vec![0.0, 1.0], vec![bad, 1.0])));
+            assert!(is_invalid_data(subplot.step(vec![0.0, bad, 2.0], vec![0.0, 1.0])));
+            assert!(is_invalid_data(subplot.step(vec![0.0, 1.0, 2.0], vec![0.0, bad])));
+        }
+        assert!(!subplot.axis_in_use(AxisType::X));
+    }
+
+    #[test]
+    fn clear_keeps_only_new_data() {
+        let mut subplot = Subplot::builder()
+            .limits(Axes::Y, Limits::Manual { min: -5.0, max: 5.0 })
+            .build();
+        subplot.plot(vec![0.0, 10.0], vec![0.0, 1.0]).unwrap();
+        subplot.fill_between(vec![0.0, 10.0], vec![0.0, 0.0], vec![1.0, 1.0]).unwrap();
+
+        subplot.clear();
+        assert!(!subplot.axis_in_use(AxisType::X));
+        assert_eq!(subplot.data_bounds(AxisType::X), None);
+
+        subplot.plot(vec![100.0, 101.0], vec![2.0, 3.0]).unwrap();
+        assert_eq!(subplot.data_bounds(AxisType::X), Some((100.0, 101.0)));
+        let (xmin, xmax) = subplot.limits(AxisType::X);
+        assert!(xmin > 10.0 && xmax > 101.0);
+        // manual limits are kept
+        assert_eq!(subplot.limits(AxisType::Y), (-5.0, 5.0));
+    }
+
+    #[test]
+    fn unused_secondary_axes_share_primary_limits() {
+        let mut subplot = Subplot::builder().build();
+        subplot.plot(vec![3.0, 7.0], vec![-20.0, 40.0]).unwrap();
+
+        assert_eq!(subplot.limits(AxisType::SecondaryX), subplot.limits(AxisType::X));
+        assert_eq!(subplot.limits(AxisType::SecondaryY), subplot.limits(AxisType::Y));
+        assert_ne!(subplot.limits(AxisType::SecondaryX), (-1.0, 1.0));
+    }
+
+    #[test]
+    fn check_limits_rejects_unusable_limits() {
+        for limits in [(5.0, 5.0), (1.0, 0.0), (f64::NAN, 1.0), (0.0, f64::INFINITY)] {
+            assert!(is_invalid_data(check_limits(AxisType::X, limits)), "{:?} accepted", limits);
+        }
+        assert!(check_limits(AxisType::X, (0.0, 1.0)).is_ok());
+    }
+
+    #[test]
+    fn try_build_rejects_zero_extent_limits() {
+        let result = Subplot::builder()
+            .limits(Axes::X, Limits::Manual { min: 5.0, max: 5.0 })
+            .try_build();
+
+        assert!(is_invalid_data(result));
+    }
+
+    #[test]
+    fn step_rejects_unordered_edges() {
+        let mut subplot = Subplot::builder().build();
+
+        assert!(is_invalid_data(subplot.step(vec![3.0, 2.0, 1.0, 0.0], vec![1.0, 2.0, 3.0])));
+        assert!(is_invalid_data(subplot.step(vec![0.0, 2.0, 1.0, 3.0], vec![1.0, 2.0, 3.0])));
+        assert!(subplot.step(vec![0.0, 1.0, 2.0, 3.0], vec![1.0, 2.0, 3.0]).is_ok());
+    }
+
+    #[test]
+    fn empty_series_are_rejected() {
+        let mut subplot = Subplot::builder().build();
+        let empty: Vec<f64> = vec![];
+
+        assert!(is_invalid_data(subplot.plot(empty.clone(), empty.clone())));
+        assert!(is_invalid_data(subplot.plot(empty.clone(), vec![1.0])));
+        assert!(is_invalid_data(subplot.plot(vec![1.0], empty.clone())));
+        assert!(is_invalid_data(subplot.step(vec![0.0], empty.clone())));
+        assert!(is_invalid_data(subplot.fill_between(empty.clone(), empty.clone(), empty.clone())));
+        assert!(!subplot.axis_in_use(AxisType::X));
+    }
+
+    #[test]
+    fn fill_between_checks_both_curves() {
+        let mut subplot = Subplot::builder().build();
+
+        assert!(is_invalid_data(subplot.fill_between(vec![0.0, 1.0], vec![0.0, 1.0], vec![0.0])));
+        assert!(is_invalid_data(
+            subplot.fill_between(vec![0.0, 1.0], vec![f64::NAN, 1.0], vec![0.0, 1.0])
+        ));
+        assert!(is_invalid_data(
+            subplot.fill_between(vec![0.0, 1.0], vec![0.0, 1.0], vec![0.0, f64::INFINITY])
+        ));
+        assert!(subplot.fill_between(vec![0.0, 1.0], vec![0.0, 1.0], vec![1.0, 2.0]).is_ok());
+    }
+
+    #[test]
+    fn removed_series_leave_other_handles_valid() {
+        let mut subplot = Subplot::builder().build();
+        let first = subplot.plot(vec![0.0, 1.0], vec![0.0, 1.0]).unwrap();
+        let removed = subplot.plot(vec![0.0, 100.0], vec![0.0, 1.0]).unwrap();
+        let last = subplot.step(vec![0.0, 1.0, 2.0], vec![0.0, 1.0]).unwrap();
+
+        subplot.remove_series(removed).unwrap();
+        assert_eq!(subplot.data_bounds(AxisType::X), Some((0.0, 2.0)));
+
+        // handles after the removed series still refer to the same series
+        subplot.update_series(first, vec![-1.0, 1.0], vec![0.0, 1.0]).unwrap();
+        subplot.update_step(last, vec![0.0, 1.0, 3.0], vec![0.0, 1.0]).unwrap();
+        assert_eq!(subplot.data_bounds(AxisType::X), Some((-1.0, 3.0)));
+
+        let invalid = |result| matches!(result, Err(PltError::InvalidSeries(1)));
+        assert!(invalid(subplot.remove_series(removed)));
+        assert!(invalid(subplot.update_series(removed, vec![0.0, 1.0], vec![0.0, 1.0])));
+        assert!(matches!(subplot.remove_series(SeriesHandle(3)), Err(PltError::InvalidSeries(3))));
+    }
+}
+